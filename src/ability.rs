@@ -0,0 +1,324 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::Path;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    boss::Boss,
+    garden::Plant,
+    interaction::{circle_path, circle_shape, CombineEvent, DragActive},
+    slime::{Slime, SlimeColor, SLIME_SIZE_MAX},
+    spider::{Spider, SpiderHealth, Webbed},
+    AppState, GameplaySystem, GARDEN_X, PLAYFIELD_WIDTH,
+};
+
+/// How far a size-1 slime's aura reaches, in pixels.
+pub const AURA_RADIUS_PX: f32 = 80.;
+/// How much further an aura reaches for every size above 1.
+pub const AURA_RADIUS_PER_SIZE_PX: f32 = 20.;
+/// Hit points a red slime burns off each spider in its aura per pulse.
+pub const BURN_DAMAGE: u32 = 1;
+pub const BURN_INTERVAL_SECS: f32 = 2.;
+/// How far a blue slime pushes the spiders in its aura back per pulse.
+pub const PUSH_DISTANCE_PX: f32 = 60.;
+pub const PUSH_INTERVAL_SECS: f32 = 3.;
+/// How fast spiders walk inside a yellow slime's aura.
+pub const SLOW_MULTIPLIER: f32 = 0.5;
+pub const FREEZE_INTERVAL_SECS: f32 = 5.;
+/// Seconds a cyan slime's pulse holds spiders still for.
+pub const FREEZE_DURATION_SECS: f32 = 1.;
+/// Hit points a green slime mends per pulse, on the plant that has the fewest.
+pub const MEND_HP: u32 = 1;
+pub const MEND_INTERVAL_SECS: f32 = 8.;
+pub const GROW_INTERVAL_SECS: f32 = 10.;
+
+/// What each color of slime does besides matching spider weaknesses, with its reach drawn
+/// around it.
+pub struct AbilityPlugin;
+
+impl Plugin for AbilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                // runs ahead of anything that might despawn the new slime this frame.
+                .with_system(add_ability.before(GameplaySystem::Combine))
+                .with_system(resize_ability_circles.after(GameplaySystem::Combine))
+                .with_system(
+                    grow_slimes
+                        .after(GameplaySystem::Defend)
+                        .before(GameplaySystem::Combine),
+                ),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .label(GameplaySystem::Abilities)
+                .after(GameplaySystem::Combine)
+                .with_system(pulse_abilities.before(GameplaySystem::Steering))
+                .with_system(ward_off_webs)
+                .with_system(
+                    hold_spiders
+                        .after(pulse_abilities)
+                        .after(GameplaySystem::Steering),
+                ),
+        );
+    }
+}
+
+/// A slime's ability, which comes from its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlimeAbility {
+    /// Red: burns [`BURN_DAMAGE`] off every spider in its aura every [`BURN_INTERVAL_SECS`],
+    /// though never the last hit point. Bosses only burn while red is their current weakness.
+    Burn,
+    /// Blue: pushes the spiders in its aura [`PUSH_DISTANCE_PX`] back every
+    /// [`PUSH_INTERVAL_SECS`].
+    Push,
+    /// Yellow: spiders in its aura walk at [`SLOW_MULTIPLIER`] times their speed.
+    Slow,
+    /// Cyan: stops the spiders in its aura for [`FREEZE_DURATION_SECS`] every
+    /// [`FREEZE_INTERVAL_SECS`].
+    Freeze,
+    /// Green: mends [`MEND_HP`] of the garden's most eaten plant every [`MEND_INTERVAL_SECS`]
+    /// while the garden is in its aura. Plants that have been eaten right up stay that way.
+    Mend,
+    /// Purple: keeps the slimes in its aura free of webs.
+    Ward,
+    /// White: grows by one size every [`GROW_INTERVAL_SECS`], up to the max.
+    Grow,
+    /// Black: comes back whole from an attack that does no damage, instead of splitting.
+    Absorb,
+}
+
+impl SlimeAbility {
+    pub fn of(color: SlimeColor) -> Self {
+        match color {
+            SlimeColor::Red => Self::Burn,
+            SlimeColor::Blue => Self::Push,
+            SlimeColor::Yellow => Self::Slow,
+            SlimeColor::Cyan => Self::Freeze,
+            SlimeColor::Green => Self::Mend,
+            SlimeColor::Purple => Self::Ward,
+            SlimeColor::White => Self::Grow,
+            SlimeColor::Black => Self::Absorb,
+        }
+    }
+
+    /// How far the ability reaches from the middle of `slime`. Abilities that only act on the
+    /// slime itself reach as far as its body.
+    pub fn radius(&self, slime: &Slime) -> f32 {
+        match self {
+            Self::Grow | Self::Absorb => slime.radius_px(),
+            _ => AURA_RADIUS_PX + AURA_RADIUS_PER_SIZE_PX * (slime.size as f32 - 1.),
+        }
+    }
+
+    /// Seconds between pulses, for the abilities that act every so often.
+    pub fn interval(&self) -> Option<f32> {
+        match self {
+            Self::Burn => Some(BURN_INTERVAL_SECS),
+            Self::Push => Some(PUSH_INTERVAL_SECS),
+            Self::Freeze => Some(FREEZE_INTERVAL_SECS),
+            Self::Mend => Some(MEND_INTERVAL_SECS),
+            Self::Grow => Some(GROW_INTERVAL_SECS),
+            Self::Slow | Self::Ward | Self::Absorb => None,
+        }
+    }
+}
+
+/// Counts down to a slime's next pulse.
+#[derive(Component)]
+pub struct AbilityTimer(pub Timer);
+
+/// The circle showing how far a slime's ability reaches, a child of its slime.
+#[derive(Component)]
+pub struct AbilityCircle;
+
+/// A spider held still by a cyan slime until the timer runs out.
+#[derive(Component)]
+pub struct Frozen(pub Timer);
+
+fn add_ability(mut commands: Commands, slime_query: Query<(Entity, &Slime), Added<Slime>>) {
+    for (entity, slime) in &slime_query {
+        let ability = SlimeAbility::of(slime.color);
+        let outline = match slime.color {
+            // black would vanish against the dark background.
+            SlimeColor::Black => Color::rgba(0.5, 0.5, 0.5, 0.4),
+            color => *color.color().set_a(0.4),
+        };
+        let circle_entity = commands
+            .spawn_bundle(circle_shape(ability.radius(slime), outline))
+            .insert(AbilityCircle)
+            .id();
+        let mut slime_entity = commands.entity(entity);
+        slime_entity.add_child(circle_entity);
+        if let Some(interval) = ability.interval() {
+            slime_entity.insert(AbilityTimer(Timer::from_seconds(interval, true)));
+        }
+    }
+}
+
+/// Fires off the abilities whose timers have come round.
+fn pulse_abilities(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut plant_query: Query<&mut Plant>,
+    mut slime_query: Query<(&Slime, &Transform, &mut AbilityTimer), Without<Spider>>,
+    mut spider_query: Query<
+        (Entity, &mut Transform, &mut SpiderHealth, Option<&Boss>),
+        With<Spider>,
+    >,
+) {
+    for (slime, slime_transform, mut timer) in &mut slime_query {
+        let ability = SlimeAbility::of(slime.color);
+        // growing has its own system, as it has to happen before merges.
+        if ability == SlimeAbility::Grow || !timer.0.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let radius = ability.radius(slime);
+        let center = slime_transform.translation.truncate();
+        if ability == SlimeAbility::Mend {
+            let most_eaten = plant_query
+                .iter_mut()
+                .filter(|plant| !plant.is_eaten())
+                .min_by_key(|plant| plant.hp);
+            if let (true, Some(mut plant)) = (center.x - radius < GARDEN_X, most_eaten) {
+                plant.grow(MEND_HP);
+            }
+            continue;
+        }
+        for (entity, mut transform, mut health, boss) in &mut spider_query {
+            if transform.translation.truncate().distance(center) > radius {
+                continue;
+            }
+            match ability {
+                // only a slime of a boss's current weakness hurts it, burning or not.
+                SlimeAbility::Burn
+                    if boss.is_none_or(|boss| boss.weakness() == SlimeColor::Red) =>
+                {
+                    health.hp = health.hp.saturating_sub(BURN_DAMAGE).max(1);
+                }
+                SlimeAbility::Push => {
+                    transform.translation.x =
+                        (transform.translation.x + PUSH_DISTANCE_PX).min(PLAYFIELD_WIDTH / 2.);
+                }
+                SlimeAbility::Freeze => {
+                    commands
+                        .entity(entity)
+                        .insert(Frozen(Timer::from_seconds(FREEZE_DURATION_SECS, false)));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Stops frozen spiders and slows the ones walking through yellow auras, on top of wherever
+/// they were steering.
+fn hold_spiders(
+    mut commands: Commands,
+    time: Res<Time>,
+    slime_query: Query<(&Slime, &Transform)>,
+    mut spider_query: Query<(Entity, &Transform, &mut Velocity, Option<&mut Frozen>), With<Spider>>,
+) {
+    let slows: Vec<(Vec2, f32)> = slime_query
+        .iter()
+        .filter(|(slime, _)| SlimeAbility::of(slime.color) == SlimeAbility::Slow)
+        .map(|(slime, transform)| {
+            (
+                transform.translation.truncate(),
+                SlimeAbility::Slow.radius(slime),
+            )
+        })
+        .collect();
+    for (entity, transform, mut velocity, frozen) in &mut spider_query {
+        if let Some(mut frozen) = frozen {
+            velocity.linvel = Vec2::ZERO;
+            if frozen.0.tick(time.delta()).just_finished() {
+                commands.entity(entity).remove::<Frozen>();
+            }
+            continue;
+        }
+        let position = transform.translation.truncate();
+        // auras don't stack.
+        if slows
+            .iter()
+            .any(|&(center, radius)| position.distance(center) <= radius)
+        {
+            velocity.linvel *= SLOW_MULTIPLIER;
+        }
+    }
+}
+
+/// Frees the slimes near a purple slime from their webs.
+fn ward_off_webs(
+    mut commands: Commands,
+    slime_query: Query<(&Slime, &Transform)>,
+    webbed_query: Query<(Entity, &Transform, &Children), (With<Slime>, With<Webbed>)>,
+    mut sprite_query: Query<&mut TextureAtlasSprite>,
+) {
+    let wards: Vec<(Vec2, f32)> = slime_query
+        .iter()
+        .filter(|(slime, _)| SlimeAbility::of(slime.color) == SlimeAbility::Ward)
+        .map(|(slime, transform)| {
+            (
+                transform.translation.truncate(),
+                SlimeAbility::Ward.radius(slime),
+            )
+        })
+        .collect();
+    for (entity, transform, children) in &webbed_query {
+        let position = transform.translation.truncate();
+        if !wards
+            .iter()
+            .any(|&(center, radius)| position.distance(center) <= radius)
+        {
+            continue;
+        }
+        commands.entity(entity).remove::<Webbed>();
+        for &child in children.iter() {
+            if let Ok(mut sprite) = sprite_query.get_mut(child) {
+                sprite.color = Color::WHITE;
+            }
+        }
+    }
+}
+
+/// Grows white slimes a size at a time, unless they're being dragged or are about to be merged
+/// or attack.
+///
+/// They grow where they are, keeping their power-up, webs and the like, and
+/// [`slime`](crate::slime) fits the rest of them to their new size.
+fn grow_slimes(
+    time: Res<Time>,
+    mut combine_events: EventReader<CombineEvent>,
+    mut slime_query: Query<(Entity, &mut Slime, &DragActive, &mut AbilityTimer)>,
+) {
+    let combining: Vec<Entity> = combine_events
+        .iter()
+        .flat_map(|ev| [ev.base, ev.addition])
+        .collect();
+    for (entity, mut slime, drag_active, mut timer) in &mut slime_query {
+        if SlimeAbility::of(slime.color) != SlimeAbility::Grow
+            || !timer.0.tick(time.delta()).just_finished()
+            || slime.size >= SLIME_SIZE_MAX
+            || drag_active.0
+            || combining.contains(&entity)
+        {
+            continue;
+        }
+        slime.size += 1;
+    }
+}
+
+/// Redraws the ability circles of slimes whose size changed.
+fn resize_ability_circles(
+    slime_query: Query<(&Slime, &Children), Changed<Slime>>,
+    mut circle_query: Query<&mut Path, With<AbilityCircle>>,
+) {
+    for (slime, children) in &slime_query {
+        for &child in children.iter() {
+            if let Ok(mut path) = circle_query.get_mut(child) {
+                *path = circle_path(SlimeAbility::of(slime.color).radius(slime));
+            }
+        }
+    }
+}
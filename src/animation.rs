@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_update(AppState::InGame).with_system(animate_sprites));
+    }
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct AnimationTimer(pub Timer);
+
+#[derive(Component)]
+pub struct SpriteAnimation {
+    pub frames: Vec<usize>,
+    pub current: usize,
+}
+
+impl SpriteAnimation {
+    pub fn slime_idle() -> Self {
+        Self {
+            frames: vec![0, 1, 2, 3],
+            current: 0,
+        }
+    }
+
+    pub fn slime_drag() -> Self {
+        Self {
+            frames: vec![24, 25, 26, 27],
+            current: 0,
+        }
+    }
+
+    pub fn spider_walk() -> Self {
+        Self {
+            frames: vec![16, 17, 18, 19, 20, 21],
+            current: 0,
+        }
+    }
+}
+
+fn animate_sprites(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut AnimationTimer,
+        &mut TextureAtlasSprite,
+        &mut SpriteAnimation,
+    )>,
+) {
+    for (mut timer, mut sprite, mut animation) in &mut query {
+        timer.tick(time.delta());
+        if timer.just_finished() {
+            animation.current = (animation.current + 1) % animation.frames.len();
+        }
+        sprite.index = animation.frames[animation.current];
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::slime::SlimeColor;
+
+pub struct AssetsPlugin;
+
+impl Plugin for AssetsPlugin {
+    fn build(&self, app: &mut App) {
+        // ahead of the startup systems that lay out tiles from these.
+        app.add_startup_system_to_stage(StartupStage::PreStartup, load_assets);
+    }
+}
+
+#[derive(Default)]
+pub struct SlimeResources {
+    pub texture_atlases: HashMap<SlimeColor, Handle<TextureAtlas>>,
+}
+
+#[derive(Default)]
+pub struct SpiderResources {
+    pub texture_atlas: Handle<TextureAtlas>,
+}
+
+#[derive(Default)]
+pub struct TileResources {
+    pub texture_atlas: Handle<TextureAtlas>,
+}
+
+#[derive(Default)]
+pub struct FontResources {
+    pub menu: Handle<Font>,
+    pub game: Handle<Font>,
+}
+
+// #[derive(Default)]
+// struct AudioResources {
+//     grab: Handle<AudioSource>,
+//     combine: Handle<AudioSource>,
+//     attack_success: Handle<AudioSource>,
+//     attack_failure: Handle<AudioSource>,
+// }
+
+fn load_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    // Load all the slime textures and insert them as a resource.
+    let mut slime_texture_atlases = HashMap::new();
+    for (color, color_str) in [
+        (SlimeColor::White, "white"),
+        (SlimeColor::Black, "black"),
+        (SlimeColor::Red, "red"),
+        (SlimeColor::Blue, "blue"),
+        (SlimeColor::Green, "green"),
+        (SlimeColor::Yellow, "yellow"),
+        (SlimeColor::Purple, "purple"),
+        (SlimeColor::Cyan, "aqua"),
+    ] {
+        let texture = asset_server.load(&format!("slime/slime_{color_str}.png"));
+        let atlas = TextureAtlas::from_grid(texture, Vec2::new(64.0, 32.0), 6, 6);
+        let atlas_handle = texture_atlases.add(atlas);
+        slime_texture_atlases.insert(color, atlas_handle);
+    }
+    commands.insert_resource(SlimeResources {
+        texture_atlases: slime_texture_atlases,
+    });
+
+    // spider resources
+    let texture = asset_server.load("spider/spider_gray.png");
+    let atlas = TextureAtlas::from_grid(texture, Vec2::new(40.0, 40.0), 8, 7);
+    let atlas_handle = texture_atlases.add(atlas);
+    commands.insert_resource(SpiderResources {
+        texture_atlas: atlas_handle,
+    });
+
+    // tile resources, 32x32 tiles laid out 8 by 8.
+    let texture = asset_server.load("tiles/TX Tileset Grass.png");
+    let atlas = TextureAtlas::from_grid(texture, Vec2::new(32.0, 32.0), 8, 8);
+    let atlas_handle = texture_atlases.add(atlas);
+    commands.insert_resource(TileResources {
+        texture_atlas: atlas_handle,
+    });
+
+    commands.insert_resource(FontResources {
+        menu: asset_server.load("fonts/Kenney Pixel.ttf"),
+        game: asset_server.load("fonts/Kenney Pixel Square.ttf"),
+    });
+
+    // commands.insert_resource(AudioResources {
+    //     grab: asset_server.load("audio/select_001.ogg"),
+    //     combine: asset_server.load("audio/drop_004.ogg"),
+    //     attack_success: asset_server.load("audio/confirmation_001.ogg"),
+    //     attack_failure: asset_server.load("audio/error_008.ogg"),
+    // });
+}
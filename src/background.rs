@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use rand::{
+    distributions::{Distribution, Uniform},
+    Rng,
+};
+
+use crate::{
+    assets::TileResources,
+    rng::{GameRng, RngStream},
+};
+
+pub struct BackgroundPlugin;
+
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_background_tiles);
+    }
+}
+
+fn spawn_background_tiles(
+    mut commands: Commands,
+    tile_resources: Res<TileResources>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    // spawn the background tiles by randomly choosing an index for each tile.
+    let background_atlas_handle = &tile_resources.texture_atlas;
+    // the grass tiles are the first four tiles of the first four rows, 4 * 4 = 16.
+    let index_distribution = Uniform::from(0..16);
+    let rng = game_rng.stream(RngStream::Background);
+    for x in -6..=10 {
+        for y in -10..=10 {
+            let index = index_distribution.sample(rng);
+            let tile_row = index / 4;
+            let tile_col = index % 4;
+            let true_index = tile_row * 8 + tile_col;
+            commands.spawn_bundle(SpriteSheetBundle {
+                sprite: TextureAtlasSprite {
+                    index: true_index,
+                    ..default()
+                },
+                texture_atlas: background_atlas_handle.clone(),
+                transform: Transform::from_translation(Vec3::new(0., 0., 1.))
+                    * Transform::from_scale(Vec3::splat(2.))
+                    * Transform::from_translation(Vec3::new(x as f32 * 32., y as f32 * 32., 0.)),
+                ..default()
+            });
+        }
+    }
+    let left_col = -10;
+    let path_col = -7;
+    for x in left_col..(left_col + 3) {
+        for y in -10..=10 {
+            let index = [12, 13, 14, 20, 21, 22, 28, 29, 30][rng.gen_range(0..9)];
+            commands.spawn_bundle(SpriteSheetBundle {
+                sprite: TextureAtlasSprite { index, ..default() },
+                texture_atlas: background_atlas_handle.clone(),
+                transform: Transform::from_translation(Vec3::new(0., 0., 1.))
+                    * Transform::from_scale(Vec3::splat(2.))
+                    * Transform::from_translation(Vec3::new(x as f32 * 32., y as f32 * 32., 0.)),
+                ..default()
+            });
+        }
+    }
+    for x in [path_col] {
+        for y in -10..=10 {
+            let index = [40, 41, 48, 49][rng.gen_range(0..4)];
+            commands.spawn_bundle(SpriteSheetBundle {
+                sprite: TextureAtlasSprite { index, ..default() },
+                texture_atlas: background_atlas_handle.clone(),
+                transform: Transform::from_translation(Vec3::new(0., 0., 1.))
+                    * Transform::from_scale(Vec3::splat(2.))
+                    * Transform::from_translation(Vec3::new(x as f32 * 32., y as f32 * 32., 0.)),
+                ..default()
+            });
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::io::Write;
+
+use bevy_jam_2::{
+    replay::{play, Replay},
+    simulation::{simulate, Bot, GreedyBot, IdleBot, LossCause, RunReport, SimulationConfig},
+    waves::WaveConfig,
+};
+
+const USAGE: &str = "usage: simulate [--runs N] [--seed S] [--bot idle|greedy] [--max-seconds T] \
+    [--waves PATH] [--out PATH] [--replay PATH]";
+
+/// Prints `message` and the usage, and exits with an error.
+fn usage_error(message: impl std::fmt::Display) -> ! {
+    eprintln!("{message}\n{USAGE}");
+    std::process::exit(2)
+}
+
+fn main() {
+    let mut runs = 100;
+    let mut first_seed = 0;
+    let mut bot: Box<dyn Bot> = Box::new(GreedyBot);
+    let mut config = SimulationConfig::default();
+    let mut out_path = None;
+    let mut replay_path = None;
+    // `--runs <n>` plays `n` games, seeded `--seed`, `--seed + 1`, and so on.
+    // `--waves <path>` plays against that ladder instead of the built-in one, e.g. a tweaked
+    // copy of `assets/waves.ron`.
+    // `--out <path>` writes the per-run CSV there instead of to stdout.
+    // `--replay <path>` plays back a replay written by the game's `--record`, and prints how
+    // it scored, instead of running any bot.
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage_error(format!("{arg} takes a value")))
+        };
+        match arg.as_str() {
+            "--runs" => {
+                runs = value()
+                    .parse()
+                    .unwrap_or_else(|_| usage_error("--runs takes an unsigned integer"))
+            }
+            "--seed" => {
+                first_seed = value()
+                    .parse()
+                    .unwrap_or_else(|_| usage_error("--seed takes an unsigned integer"))
+            }
+            "--bot" => {
+                bot = match value().as_str() {
+                    "idle" => Box::new(IdleBot),
+                    "greedy" => Box::new(GreedyBot),
+                    other => usage_error(format!("unknown bot {other}")),
+                }
+            }
+            "--max-seconds" => {
+                config.max_seconds = value()
+                    .parse()
+                    .unwrap_or_else(|_| usage_error("--max-seconds takes a number"))
+            }
+            "--waves" => {
+                let path = value();
+                let ron = std::fs::read_to_string(&path)
+                    .unwrap_or_else(|err| usage_error(format!("could not read {path}: {err}")));
+                config.waves = WaveConfig::from_ron(&ron)
+                    .unwrap_or_else(|err| usage_error(format!("could not parse {path}: {err}")));
+            }
+            "--out" => out_path = Some(value()),
+            "--replay" => replay_path = Some(value()),
+            _ => usage_error(format!("unknown argument {arg}")),
+        }
+    }
+
+    if let Some(path) = replay_path {
+        let ron = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| usage_error(format!("could not read {path}: {err}")));
+        let replay = Replay::from_ron(&ron)
+            .unwrap_or_else(|err| usage_error(format!("could not parse {path}: {err}")));
+        let seed = replay.seed;
+        let score = play(replay);
+        println!(
+            "seed {seed}: {} points, {} of {} spiders killed, best streak {}, {} close calls, \
+             {:.1} seconds survived",
+            score.points,
+            score.spiders_killed,
+            score.spiders_spawned,
+            score.best_streak,
+            score.close_calls,
+            score.seconds_survived,
+        );
+        return;
+    }
+
+    let mut out: Box<dyn Write> = match &out_path {
+        Some(path) => Box::new(
+            std::fs::File::create(path)
+                .unwrap_or_else(|err| usage_error(format!("could not create {path}: {err}"))),
+        ),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    writeln!(
+        out,
+        "seed,bot,cause,survival_seconds,spiders_killed,spiders_spawned,\
+         losing_spider_level,losing_spider_weakness,losing_spider_kind"
+    )
+    .unwrap();
+    let mut reports = Vec::with_capacity(runs);
+    for i in 0..runs {
+        let report = simulate(first_seed + i as u64, bot.as_mut(), &config);
+        let (cause, level, weakness, kind) = match report.cause {
+            LossCause::GardenReached { spider } => (
+                "garden",
+                spider.level.to_string(),
+                spider.weakness.name(),
+                spider.kind.name(),
+            ),
+            LossCause::Timeout => ("timeout", String::new(), "", ""),
+        };
+        writeln!(
+            out,
+            "{},{},{},{:.3},{},{},{},{},{}",
+            report.seed,
+            bot.name(),
+            cause,
+            report.survival_seconds,
+            report.spiders_killed,
+            report.spiders_spawned,
+            level,
+            weakness,
+            kind,
+        )
+        .unwrap();
+        reports.push(report);
+    }
+    out.flush().unwrap();
+    print_summary(bot.name(), &reports);
+}
+
+type Metric = fn(&RunReport) -> f64;
+
+fn print_summary(bot: &str, reports: &[RunReport]) {
+    if reports.is_empty() {
+        return;
+    }
+    let timeouts = reports
+        .iter()
+        .filter(|report| report.cause == LossCause::Timeout)
+        .count();
+    eprintln!(
+        "{} runs with the {bot} bot: {} reached the garden, {timeouts} timed out",
+        reports.len(),
+        reports.len() - timeouts,
+    );
+    eprintln!(
+        "{:<18} {:>10} {:>10} {:>10} {:>10}",
+        "", "mean", "p10", "p50", "p90"
+    );
+    let metrics: [(&str, Metric); 3] = [
+        ("survival seconds", |report| report.survival_seconds),
+        ("spiders killed", |report| report.spiders_killed as f64),
+        ("spiders spawned", |report| report.spiders_spawned as f64),
+    ];
+    for (name, metric) in metrics {
+        let mut values: Vec<f64> = reports.iter().map(metric).collect();
+        values.sort_by(f64::total_cmp);
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        eprintln!(
+            "{name:<18} {mean:>10.2} {:>10.2} {:>10.2} {:>10.2}",
+            percentile(&values, 0.1),
+            percentile(&values, 0.5),
+            percentile(&values, 0.9),
+        );
+    }
+}
+
+/// Nearest-rank percentile of already sorted `values`.
+fn percentile(values: &[f64], p: f64) -> f64 {
+    let rank = (p * values.len() as f64).ceil() as usize;
+    values[rank.clamp(1, values.len()) - 1]
+}
@@ -0,0 +1,279 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    assets::FontResources,
+    camera::PlayfieldAnchor,
+    combine::{attacker_remains, CombineOutcome, WEAKNESS_DAMAGE_MULTIPLIER},
+    pathing::SpiderMovement,
+    slime::{Slime, SlimeColor},
+    spider::{Spider, SpiderHealth, SpiderKind},
+    waves::BossWave,
+    AppState, GameplaySystem, PLAYFIELD_WIDTH,
+};
+
+/// Shows the health bar of whichever boss is on the field.
+///
+/// Bosses themselves are spiders with a [`Boss`] component: [`crate::spider::SpiderPlugin`]
+/// spawns them at the milestones in [`crate::waves::WaveConfig::bosses`], and
+/// [`crate::combine::CombinePlugin`] takes them through their phases.
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(add_boss_health_bar.after(GameplaySystem::Combine))
+                .with_system(show_boss_health.after(add_boss_health_bar)),
+        )
+        .add_system_set(
+            SystemSet::on_enter(AppState::GameOver).with_system(despawn_boss_health_bars),
+        );
+    }
+}
+
+/// The tint that sets bosses apart from ordinary spiders.
+pub const BOSS_TINT: Color = Color::rgb(1., 0.45, 0.45);
+
+/// A spider with several weaknesses, each of which has to be worn down in turn.
+///
+/// Only a slime of the current phase's weakness hurts it. Emptying a phase's hit points moves
+/// it on to the next weakness with fresh hit points, and summons minions; emptying the last
+/// one kills it.
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub struct Boss {
+    /// One weakness per phase, in the order they have to be hit.
+    pub weaknesses: Vec<SlimeColor>,
+    pub phase: usize,
+    pub hp_per_phase: u32,
+    /// How many spiders it summons when it moves on to its next phase.
+    pub minions: u32,
+    pub minion_level: u32,
+}
+
+impl Boss {
+    /// Draws a boss for `wave` from `rng`, with no weakness the same as the one before it.
+    pub fn from_wave(wave: &BossWave, rng: &mut impl Rng) -> Self {
+        let mut weaknesses: Vec<SlimeColor> = Vec::with_capacity(wave.phases);
+        while weaknesses.len() < wave.phases {
+            let color = SlimeColor::ALL[rng.gen_range(0..8)];
+            if weaknesses.last() != Some(&color) {
+                weaknesses.push(color);
+            }
+        }
+        Self {
+            weaknesses,
+            phase: 0,
+            hp_per_phase: wave.hp_per_phase,
+            minions: wave.minions,
+            minion_level: wave.minion_level,
+        }
+    }
+
+    /// The spider that carries this boss, walking at `speed`.
+    pub fn spider(&self, level: u32, speed: f32) -> Spider {
+        Spider {
+            level,
+            weakness: self.weakness(),
+            speed,
+            kind: SpiderKind::Common,
+            movement: SpiderMovement::Straight,
+        }
+    }
+
+    /// The weakness of the current phase.
+    pub fn weakness(&self) -> SlimeColor {
+        self.weaknesses[self.phase]
+    }
+
+    pub fn is_last_phase(&self) -> bool {
+        self.phase + 1 >= self.weaknesses.len()
+    }
+
+    /// Hit points for a fresh phase.
+    pub fn phase_health(&self) -> SpiderHealth {
+        SpiderHealth {
+            hp: self.hp_per_phase,
+            max_hp: self.hp_per_phase,
+        }
+    }
+
+    /// How much of its health across every phase is left, from 0 to 1.
+    pub fn fraction(&self, health: &SpiderHealth) -> f32 {
+        let phases_left = self.weaknesses.len() - self.phase - 1;
+        let total = self.weaknesses.len() as u32 * self.hp_per_phase;
+        (phases_left as u32 * self.hp_per_phase + health.hp) as f32 / total.max(1) as f32
+    }
+
+    /// Moves on to the next phase: its spider takes on the next weakness, with fresh health.
+    pub fn next_phase(&mut self, spider: &mut Spider, health: &mut SpiderHealth) {
+        self.phase = (self.phase + 1).min(self.weaknesses.len() - 1);
+        spider.weakness = self.weakness();
+        *health = self.phase_health();
+    }
+}
+
+/// Attacks the boss carried by `spider`, which has `health` left in its current phase, with
+/// `slime`.
+///
+/// Slimes of any color but the current weakness do nothing. Clearing a phase other than the
+/// last summons the boss's minions, which share the weakness of the phase to come; the caller
+/// moves the boss on with [`Boss::next_phase`]. Either way the slime is left as
+/// [`attacker_remains`] says.
+pub fn attack_boss(
+    spider: &Spider,
+    health: &SpiderHealth,
+    boss: &Boss,
+    slime: &Slime,
+) -> CombineOutcome {
+    let spider_damage = if slime.color == boss.weakness() {
+        slime.size * WEAKNESS_DAMAGE_MULTIPLIER
+    } else {
+        0
+    };
+    let phase_cleared = spider_damage >= health.hp;
+    let spider_killed = phase_cleared && boss.is_last_phase();
+    let brood = if phase_cleared && !spider_killed {
+        let minion = Spider {
+            level: boss.minion_level,
+            weakness: boss.weaknesses[boss.phase + 1],
+            kind: SpiderKind::Common,
+            ..*spider
+        };
+        vec![minion; boss.minions as usize]
+    } else {
+        Vec::new()
+    };
+    CombineOutcome {
+        spawned: attacker_remains(slime, spider_damage),
+        brood,
+        spider_damage,
+        spider_killed,
+        score_delta: u32::from(spider_killed),
+    }
+}
+
+/// The health bar across the top of the screen, for the boss it belongs to.
+#[derive(Component)]
+pub struct BossHealthBar(pub Entity);
+
+/// The part of a [`BossHealthBar`] that shrinks as the boss is hurt.
+#[derive(Component)]
+struct BossHealthFill;
+
+/// The "BOSS phase n/m" caption above a [`BossHealthBar`].
+#[derive(Component)]
+struct BossHealthText;
+
+fn add_boss_health_bar(
+    mut commands: Commands,
+    fonts: Res<FontResources>,
+    boss_query: Query<Entity, Added<Boss>>,
+) {
+    for boss in &boss_query {
+        commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Px(64.0)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(8.0),
+                        ..default()
+                    },
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::ColumnReverse,
+                    ..default()
+                },
+                color: Color::NONE.into(),
+                ..default()
+            })
+            .insert(PlayfieldAnchor {
+                offset: Vec2::new(0.0, 8.0),
+                size: Some(Vec2::new(PLAYFIELD_WIDTH, 64.0)),
+            })
+            .insert(BossHealthBar(boss))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: fonts.game.clone(),
+                            font_size: 24.,
+                            color: BOSS_TINT,
+                        },
+                    ))
+                    .insert(BossHealthText);
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(50.0), Val::Px(16.0)),
+                            ..default()
+                        },
+                        color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent
+                            .spawn_bundle(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                                    ..default()
+                                },
+                                color: BOSS_TINT.into(),
+                                ..default()
+                            })
+                            .insert(BossHealthFill);
+                    });
+            });
+    }
+}
+
+/// Keeps each boss health bar in step with its boss, and takes it down once the boss is gone.
+fn show_boss_health(
+    mut commands: Commands,
+    bar_query: Query<(Entity, &BossHealthBar)>,
+    boss_query: Query<(&Boss, &SpiderHealth)>,
+    children_query: Query<&Children>,
+    mut fill_query: Query<&mut Style, With<BossHealthFill>>,
+    mut text_query: Query<&mut Text, With<BossHealthText>>,
+) {
+    for (bar, BossHealthBar(boss)) in &bar_query {
+        let Ok((boss, health)) = boss_query.get(*boss) else {
+            commands.entity(bar).despawn_recursive();
+            continue;
+        };
+        for descendant in iter_descendants(bar, &children_query) {
+            if let Ok(mut style) = fill_query.get_mut(descendant) {
+                style.size.width = Val::Percent(100. * boss.fraction(health));
+            }
+            if let Ok(mut text) = text_query.get_mut(descendant) {
+                text.sections[0].value = format!(
+                    "BOSS  phase {}/{}  {}",
+                    boss.phase + 1,
+                    boss.weaknesses.len(),
+                    boss.weakness().name()
+                );
+                text.sections[0].style.color = boss.weakness().color();
+            }
+        }
+    }
+}
+
+fn iter_descendants(entity: Entity, children_query: &Query<&Children>) -> Vec<Entity> {
+    let mut descendants = Vec::new();
+    let mut stack = vec![entity];
+    while let Some(entity) = stack.pop() {
+        if let Ok(children) = children_query.get(entity) {
+            descendants.extend(children.iter().copied());
+            stack.extend(children.iter().copied());
+        }
+    }
+    descendants
+}
+
+fn despawn_boss_health_bars(mut commands: Commands, query: Query<Entity, With<BossHealthBar>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
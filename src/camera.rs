@@ -0,0 +1,162 @@
+use bevy::{prelude::*, render::camera::ScalingMode, window::WindowResized};
+
+use crate::{PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH};
+
+/// Shows the whole playfield, scaled to fit the window, with bars over whatever is left.
+///
+/// The camera's projection does the scaling, so nothing in the world needs to know about it.
+/// UI nodes are laid out in window pixels instead, so the ones that belong on the playfield
+/// carry a [`PlayfieldAnchor`] to keep them there.
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_camera)
+            .add_startup_system(spawn_letterbox_bars)
+            .init_resource::<Letterbox>()
+            .add_startup_system(fit_letterbox_to_window)
+            .add_system(fit_letterbox_on_resize)
+            .add_system(anchor_to_playfield.after(fit_letterbox_on_resize));
+    }
+}
+
+#[derive(Component)]
+pub struct MainCamera;
+
+/// Where the playfield sits in the window: `offset` is its top left corner and `scale` how many
+/// logical window pixels one world unit takes up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Letterbox {
+    pub offset: Vec2,
+    pub scale: f32,
+}
+
+impl Default for Letterbox {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            scale: 1.,
+        }
+    }
+}
+
+impl Letterbox {
+    /// Fits the playfield into a window of `size` logical pixels, centred along the axis it
+    /// doesn't fill.
+    pub fn fit(size: Vec2) -> Self {
+        let playfield = Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT);
+        let scale = (size / playfield).min_element();
+        Self {
+            offset: (size - playfield * scale) / 2.,
+            scale,
+        }
+    }
+}
+
+/// Pins a UI node `offset` world units from the playfield's top left corner and, if it has a
+/// `size`, makes it span that many world units of the playfield, whatever the window's shape.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct PlayfieldAnchor {
+    pub offset: Vec2,
+    pub size: Option<Vec2>,
+}
+
+impl PlayfieldAnchor {
+    /// Pins a node `left` and `top` world units into the playfield.
+    pub fn at(left: f32, top: f32) -> Self {
+        Self {
+            offset: Vec2::new(left, top),
+            size: None,
+        }
+    }
+
+    /// Spans the whole playfield.
+    pub fn fill() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            size: Some(Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT)),
+        }
+    }
+
+    /// Where the node's top left corner goes in UI coordinates, given the `letterbox`.
+    pub fn position(&self, letterbox: &Letterbox) -> Vec2 {
+        letterbox.offset + self.offset * letterbox.scale
+    }
+
+    /// How many logical window pixels the node spans, if it spans any part of the playfield.
+    pub fn size(&self, letterbox: &Letterbox) -> Option<Vec2> {
+        self.size.map(|size| size * letterbox.scale)
+    }
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands
+        .spawn_bundle(Camera2dBundle {
+            projection: OrthographicProjection {
+                scaling_mode: ScalingMode::Auto {
+                    min_width: PLAYFIELD_WIDTH,
+                    min_height: PLAYFIELD_HEIGHT,
+                },
+                ..default()
+            },
+            ..default()
+        })
+        .insert(MainCamera);
+}
+
+fn spawn_letterbox_bars(mut commands: Commands) {
+    // far bigger than any window could show past the playfield.
+    let size = 4. * Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT);
+    for direction in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+        let center = direction * (Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT) + size) / 2.;
+        commands.spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::BLACK,
+                custom_size: Some(size),
+                ..default()
+            },
+            transform: Transform::from_translation(center.extend(100.)),
+            ..default()
+        });
+    }
+}
+
+fn fit_letterbox_to_window(windows: Res<Windows>, mut letterbox: ResMut<Letterbox>) {
+    if let Some(window) = windows.get_primary() {
+        *letterbox = Letterbox::fit(Vec2::new(window.width(), window.height()));
+    }
+}
+
+fn fit_letterbox_on_resize(
+    mut events: EventReader<WindowResized>,
+    mut letterbox: ResMut<Letterbox>,
+) {
+    for ev in events.iter() {
+        if ev.id.is_primary() {
+            *letterbox = Letterbox::fit(Vec2::new(ev.width, ev.height));
+        }
+    }
+}
+
+/// Lays out anchored nodes when they show up, and again whenever the window changes shape.
+fn anchor_to_playfield(
+    letterbox: Res<Letterbox>,
+    mut query: Query<(
+        &PlayfieldAnchor,
+        ChangeTrackers<PlayfieldAnchor>,
+        &mut Style,
+    )>,
+) {
+    for (anchor, tracker, mut style) in &mut query {
+        if !letterbox.is_changed() && !tracker.is_added() {
+            continue;
+        }
+        let position = anchor.position(&letterbox);
+        style.position_type = PositionType::Absolute;
+        style.position.left = Val::Px(position.x);
+        style.position.top = Val::Px(position.y);
+        if let Some(size) = anchor.size(&letterbox) {
+            style.size = Size::new(Val::Px(size.x), Val::Px(size.y));
+        }
+    }
+}
@@ -0,0 +1,453 @@
+use std::marker::PhantomData;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    boss::{attack_boss, Boss},
+    interaction::CombineEvent,
+    loot::{empowered, PowerUp},
+    rng::{GameRng, RngStream},
+    score::ScoreResource,
+    slime::{Slime, SlimeColor, SpawnSlimeEvent, SLIME_SIZE_MAX, SLIME_SIZE_MIN},
+    spider::{
+        SpawnSpiderEvent, Spider, SpiderHealth, SpiderKind, ARMORED_MIN_SLIME_SIZE, BROOD_SIZE,
+    },
+    splash::{splash, SplashConfig, SplashEvent, SplashTarget},
+    AppState, GameplaySystem,
+};
+
+/// Resolves slimes dropped onto other slimes or onto spiders.
+pub struct CombinePlugin;
+
+impl Plugin for CombinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SpiderKilledEvent>()
+            .init_resource::<ColorMixing>()
+            .init_resource::<OverflowPolicy>()
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame).with_system(
+                    combine
+                        .label(GameplaySystem::Combine)
+                        .after(GameplaySystem::Drag),
+                ),
+            );
+    }
+}
+
+/// Sent for every spider that a slime kills, bosses included.
+pub struct SpiderKilledEvent {
+    pub spider: Spider,
+    pub position: Vec2,
+}
+
+/// Which color two merging slimes of different colors make.
+///
+/// Insert one before adding [`CombinePlugin`] to play with a different table. Pairs that aren't
+/// in the table, as well as two slimes of the same color, keep the color of the slime that was
+/// dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ColorMixing {
+    pub mixes: Vec<ColorMix>,
+}
+
+/// Merging `colors` in either order makes `result`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct ColorMix {
+    pub colors: (SlimeColor, SlimeColor),
+    pub result: SlimeColor,
+}
+
+impl Default for ColorMixing {
+    fn default() -> Self {
+        use SlimeColor::*;
+        let mix = |a, b, result| ColorMix {
+            colors: (a, b),
+            result,
+        };
+        Self {
+            mixes: vec![
+                // paint mixing.
+                mix(Red, Blue, Purple),
+                mix(Blue, Yellow, Green),
+                mix(Blue, Green, Cyan),
+                mix(Red, Green, Yellow),
+                // white lightens, undoing what black does,
+                mix(White, Blue, Cyan),
+                mix(White, Green, Yellow),
+                mix(White, Purple, Red),
+                // and black darkens.
+                mix(Black, Cyan, Blue),
+                mix(Black, Yellow, Green),
+                mix(Black, Red, Purple),
+            ],
+        }
+    }
+}
+
+impl ColorMixing {
+    pub fn from_ron(ron: &str) -> ron::Result<Self> {
+        ron::from_str(ron)
+    }
+
+    /// The color of `addition` merged into `base`.
+    pub fn mix(&self, base: SlimeColor, addition: SlimeColor) -> SlimeColor {
+        self.mixes
+            .iter()
+            .find(|mix| mix.colors == (base, addition) || mix.colors == (addition, base))
+            .map_or(addition, |mix| mix.result)
+    }
+}
+
+/// How far a mega slime can grow under [`OverflowPolicy::Mega`].
+pub const SLIME_MEGA_SIZE_MAX: u32 = 2 * SLIME_SIZE_MAX;
+
+/// What a merge bigger than [`SLIME_SIZE_MAX`] turns into.
+///
+/// It's fixed for the length of a run, so insert one before the run starts to play a different
+/// mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OverflowPolicy {
+    /// Two slimes that make up the max size, one for the overflow, and a size-1 slime of a
+    /// random color. The overflow slime is never bigger than the max size, so very big merges
+    /// lose some of their size, and small ones gain some.
+    #[default]
+    Shatter,
+    /// One slime of the max size; the rest is lost.
+    Cap,
+    /// As few slimes as can hold the whole size without going over the max, as close to the
+    /// same size as they can be.
+    Split,
+    /// One mega slime of up to [`SLIME_MEGA_SIZE_MAX`], with whatever is left over in a second
+    /// one.
+    Mega,
+}
+
+impl OverflowPolicy {
+    /// The sizes a merge of `size`, which is over [`SLIME_SIZE_MAX`], breaks up into, each with
+    /// whether that slime takes a random color rather than the merged one.
+    pub fn sizes(&self, size: u32) -> Vec<(u32, bool)> {
+        match self {
+            Self::Shatter => vec![
+                (SLIME_SIZE_MAX / 2, false),
+                (SLIME_SIZE_MAX - SLIME_SIZE_MAX / 2, false),
+                (
+                    (size - SLIME_SIZE_MAX).clamp(SLIME_SIZE_MIN, SLIME_SIZE_MAX),
+                    false,
+                ),
+                (1, true),
+            ],
+            Self::Cap => vec![(SLIME_SIZE_MAX, false)],
+            Self::Split => even_split(size, SLIME_SIZE_MAX),
+            Self::Mega => even_split(size, SLIME_MEGA_SIZE_MAX),
+        }
+    }
+}
+
+/// Splits `size` into as few parts of at most `max` as it takes, none more than one apart.
+fn even_split(size: u32, max: u32) -> Vec<(u32, bool)> {
+    let parts = size.div_ceil(max);
+    (0..parts)
+        .map(|part| (size / parts + u32::from(part < size % parts), false))
+        .collect()
+}
+
+/// What happens when one thing is dropped onto another.
+///
+/// The dropped slime is always used up, and so is the base slime when two slimes merge.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CombineOutcome {
+    /// New slimes to spawn around the drop point.
+    pub spawned: Vec<Slime>,
+    /// New spiders to spawn around the drop point, hatched from a brood mother.
+    pub brood: Vec<Spider>,
+    /// How many hit points the spider that was attacked loses.
+    pub spider_damage: u32,
+    /// Whether the spider that was attacked is defeated.
+    pub spider_killed: bool,
+    /// How much to add to [`ScoreResource::spiders_killed`].
+    pub score_delta: u32,
+}
+
+/// Merges `addition` into `base`.
+///
+/// The merged slime's color comes from `mixing`. A merge bigger than [`SLIME_SIZE_MAX`]
+/// breaks up as `overflow` says, with any slime of a random color drawn from `rng`.
+pub fn merge_slimes(
+    base: &Slime,
+    addition: &Slime,
+    mixing: &ColorMixing,
+    overflow: OverflowPolicy,
+    rng: &mut impl Rng,
+) -> CombineOutcome {
+    let new_size = base.size + addition.size;
+    let new_color = mixing.mix(base.color, addition.color);
+    // drawn whether or not it's needed, so that every policy leaves the rng in the same state.
+    let random_color = SlimeColor::ALL[rng.gen_range(0..8)];
+    let spawned = if new_size > SLIME_SIZE_MAX {
+        overflow
+            .sizes(new_size)
+            .into_iter()
+            .map(|(size, random)| Slime {
+                color: if random { random_color } else { new_color },
+                size,
+            })
+            .collect()
+    } else {
+        vec![Slime {
+            color: new_color,
+            size: new_size,
+        }]
+    };
+    CombineOutcome {
+        spawned,
+        ..default()
+    }
+}
+
+/// How many times its size a slime of a spider's weakness color hits for.
+pub const WEAKNESS_DAMAGE_MULTIPLIER: u32 = 2;
+
+/// How many hit points `slime` takes off `spider` in an attack.
+///
+/// That's the slime's size, or [`WEAKNESS_DAMAGE_MULTIPLIER`] times that if it matches the
+/// spider's weakness, and nothing at all if the spider is armored and the slime is smaller than
+/// [`ARMORED_MIN_SLIME_SIZE`].
+pub fn spider_damage(spider: &Spider, slime: &Slime) -> u32 {
+    if spider.kind == SpiderKind::Armored && slime.size < ARMORED_MIN_SLIME_SIZE {
+        0
+    } else if spider.weakness == slime.color {
+        slime.size * WEAKNESS_DAMAGE_MULTIPLIER
+    } else {
+        slime.size
+    }
+}
+
+/// Attacks `spider`, which has `health` left, with `slime`.
+///
+/// The spider is defeated when the [`spider_damage`] uses up its hit points, and then
+/// [`hatch`]es. Either way the slime is left as [`attacker_remains`] says.
+pub fn attack_spider(spider: &Spider, health: &SpiderHealth, slime: &Slime) -> CombineOutcome {
+    let spider_damage = spider_damage(spider, slime);
+    let spider_killed = spider_damage >= health.hp;
+    let brood = if spider_killed {
+        hatch(spider)
+    } else {
+        Vec::new()
+    };
+    CombineOutcome {
+        spawned: attacker_remains(slime, spider_damage),
+        brood,
+        spider_damage,
+        spider_killed,
+        score_delta: u32::from(spider_killed),
+    }
+}
+
+/// What comes out of `spider` when it dies: [`BROOD_SIZE`] common spiders a level lower if it's
+/// a brood mother above level 1, and nothing otherwise.
+pub fn hatch(spider: &Spider) -> Vec<Spider> {
+    if spider.kind == SpiderKind::BroodMother && spider.level > 1 {
+        vec![
+            Spider {
+                level: spider.level - 1,
+                kind: SpiderKind::Common,
+                ..*spider
+            };
+            BROOD_SIZE
+        ]
+    } else {
+        Vec::new()
+    }
+}
+
+/// What's left of `slime` after an attack that did `damage`: it splits into two halves, unless
+/// it's black and the attack did no damage at all, in which case it soaks up the blow and comes
+/// back whole.
+pub fn attacker_remains(slime: &Slime, damage: u32) -> Vec<Slime> {
+    if slime.color == SlimeColor::Black && damage == 0 {
+        return vec![*slime];
+    }
+    [slime.size / 2, slime.size - slime.size / 2]
+        .into_iter()
+        .filter(|&size| size > 0)
+        .map(|size| Slime {
+            color: slime.color,
+            size,
+        })
+        .collect()
+}
+
+/// The settings a run's merges and attacks play by.
+#[derive(SystemParam)]
+struct CombineRules<'w, 's> {
+    mixing: Res<'w, ColorMixing>,
+    overflow: Res<'w, OverflowPolicy>,
+    splash: Res<'w, SplashConfig>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+/// Where the outcomes of merges and attacks go.
+#[derive(SystemParam)]
+struct CombineOutput<'w, 's> {
+    score: ResMut<'w, ScoreResource>,
+    slime_events: EventWriter<'w, 's, SpawnSlimeEvent>,
+    spider_events: EventWriter<'w, 's, SpawnSpiderEvent>,
+    killed_events: EventWriter<'w, 's, SpiderKilledEvent>,
+    splash_events: EventWriter<'w, 's, SplashEvent>,
+}
+
+fn combine(
+    mut commands: Commands,
+    rules: CombineRules,
+    mut game_rng: ResMut<GameRng>,
+    mut combine_events: EventReader<CombineEvent>,
+    // audio: Res<Audio>,
+    // audio_resources: Res<AudioResources>,
+    slime_query: Query<(&Slime, Option<&PowerUp>)>,
+    mut spider_query: Query<(
+        Entity,
+        &Transform,
+        &mut Spider,
+        &mut SpiderHealth,
+        Option<&mut Boss>,
+    )>,
+    mut output: CombineOutput,
+) {
+    let rng = game_rng.stream(RngStream::Combine);
+    // despawns wait for the end of the frame, so keep track of what's already been used up lest
+    // a defender and the player, say, both spend it.
+    let mut consumed: Vec<Entity> = Vec::new();
+    for ev in combine_events.iter() {
+        if consumed.contains(&ev.base) || consumed.contains(&ev.addition) {
+            continue;
+        }
+        // the slime and spider of a kill, which may go on to splash.
+        let (outcome, kill) = if let Ok([(base_slime, _), (addition_slime, _)]) =
+            slime_query.get_many([ev.base, ev.addition])
+        {
+            // audio.play(audio_resources.combine.clone());
+            commands.entity(ev.base).despawn_recursive();
+            consumed.push(ev.base);
+            let outcome = merge_slimes(
+                base_slime,
+                addition_slime,
+                &rules.mixing,
+                *rules.overflow,
+                rng,
+            );
+            (outcome, None)
+        } else if let (Ok((_, _, mut spider, mut health, boss)), Ok((slime, power_up))) =
+            (spider_query.get_mut(ev.base), slime_query.get(ev.addition))
+        {
+            // killed earlier this frame.
+            if health.hp == 0 {
+                continue;
+            }
+            let attacker = empowered(slime, power_up, spider.weakness);
+            let mut outcome = match &boss {
+                Some(boss) => attack_boss(&spider, &health, boss, &attacker),
+                None => attack_spider(&spider, &health, &attacker),
+            };
+            // power-ups only last as long as the slime, so what's left of it is its plain self.
+            outcome.spawned = attacker_remains(slime, outcome.spider_damage);
+            health.hp = health.hp.saturating_sub(outcome.spider_damage);
+            output.score.attack(outcome.spider_damage);
+            if outcome.spider_killed {
+                output.score.kill(&spider, ev.location);
+                // audio.play(audio_resources.attack_success.clone());
+                commands.entity(ev.base).despawn_recursive();
+                output.killed_events.send(SpiderKilledEvent {
+                    spider: *spider,
+                    position: ev.location,
+                });
+            } else if let (Some(mut boss), 0) = (boss, health.hp) {
+                boss.next_phase(&mut spider, &mut health);
+            } else {
+                // audio.play(audio_resources.attack_failure.clone());
+            }
+            let kill = outcome.spider_killed.then_some((attacker, *spider));
+            (outcome, kill)
+        } else {
+            continue;
+        };
+        consumed.push(ev.addition);
+        output.score.spiders_killed += outcome.score_delta;
+        let mut spawned: Vec<(Slime, Vec2)> = outcome
+            .spawned
+            .into_iter()
+            .map(|slime| (slime, ev.location))
+            .collect();
+        let mut brood: Vec<(Spider, Vec2)> = outcome
+            .brood
+            .into_iter()
+            .map(|spider| (spider, ev.location))
+            .collect();
+        if let Some((slime, spider)) = kill
+            .filter(|(slime, spider)| rules.splash.enabled && rules.splash.splashes(slime, spider))
+        {
+            let targets: Vec<SplashTarget> = spider_query
+                .iter()
+                .filter(|(entity, _, _, health, boss)| {
+                    *entity != ev.base && health.hp > 0 && boss.is_none()
+                })
+                .map(|(entity, transform, spider, health, _)| SplashTarget {
+                    entity,
+                    position: transform.translation.truncate(),
+                    spider: *spider,
+                    hp: health.hp,
+                })
+                .collect();
+            let remains = spawned.iter().map(|&(slime, _)| slime).collect();
+            let chain = splash(
+                &rules.splash,
+                &slime,
+                remains,
+                &spider,
+                ev.location,
+                &targets,
+            );
+            for (entity, damage) in chain.damage {
+                if let Ok((.., mut health, _)) = spider_query.get_mut(entity) {
+                    health.hp = health.hp.saturating_sub(damage);
+                }
+            }
+            for target in chain.killed {
+                commands.entity(target.entity).despawn_recursive();
+                output.score.attack(target.hp);
+                output.score.kill(&target.spider, target.position);
+                output.score.spiders_killed += 1;
+                output.killed_events.send(SpiderKilledEvent {
+                    spider: target.spider,
+                    position: target.position,
+                });
+            }
+            for position in chain.splashes {
+                output.splash_events.send(SplashEvent {
+                    position,
+                    color: slime.color.color(),
+                });
+            }
+            spawned = chain.landed;
+            brood.extend(chain.brood);
+        }
+        for (slime, position) in spawned {
+            let offset = Vec2::new(rng.gen(), rng.gen()) * 20.;
+            output.slime_events.send(SpawnSlimeEvent {
+                slime,
+                position: position + offset,
+            });
+        }
+        for (spider, position) in brood {
+            let offset = (Vec2::new(rng.gen(), rng.gen()) - 0.5) * 80.;
+            output.spider_events.send(SpawnSpiderEvent {
+                spider,
+                position: position + offset,
+                boss: None,
+            });
+        }
+        commands.entity(ev.addition).despawn_recursive();
+    }
+}
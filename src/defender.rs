@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    interaction::{ActivationCircle, CombineEvent, DragActive},
+    slime::Slime,
+    spider::{Spider, Webbed, SPIDER_RADIUS_PX},
+    AppState, GameplaySystem,
+};
+
+/// Lets big enough slimes go after spiders of their own color by themselves, once
+/// [`DefenderConfig::enabled`] is switched on.
+///
+/// A defender picks the closest matching spider in range, outlines itself in its color while it
+/// winds up and closes in, then attacks exactly as if it had been dropped on the spider.
+pub struct DefenderPlugin;
+
+impl Plugin for DefenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DefenderConfig>().add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(
+                    defend
+                        .label(GameplaySystem::Defend)
+                        .after(GameplaySystem::Drag)
+                        .before(GameplaySystem::Combine),
+                )
+                .with_system(telegraph_defenders.after(defend)),
+        );
+    }
+}
+
+/// Whether slimes defend on their own, and how.
+///
+/// It's off unless the player asks for it, and is fixed for the length of a run so that
+/// replays play out the same.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefenderConfig {
+    pub enabled: bool,
+    /// Smaller slimes leave the fighting to the player.
+    pub min_size: u32,
+    /// How far away a spider can be for a defender to go after it, in pixels.
+    pub range_px: f32,
+    /// Seconds a defender rests between attacks, and after it first shows up.
+    pub cooldown_secs: f32,
+    /// Seconds a defender telegraphs before it can strike.
+    pub windup_secs: f32,
+    /// How fast a defender closes in on its target, in pixels per second.
+    pub speed: f32,
+}
+
+impl Default for DefenderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_size: 3,
+            range_px: 250.,
+            cooldown_secs: 4.,
+            windup_secs: 0.75,
+            speed: 250.,
+        }
+    }
+}
+
+/// A slime's defending state, which every slime has whether or not defenders are enabled.
+///
+/// It only changes when the slime takes up or gives up a target, so that its outline is only
+/// redrawn then; the timers that tick every frame are kept apart in [`DefenderTimers`].
+#[derive(Component, Default)]
+pub struct Defender {
+    /// The spider it's going after.
+    pub engaging: Option<Entity>,
+}
+
+/// How much longer a defender rests for, and winds up for once it has picked a target.
+#[derive(Component)]
+pub struct DefenderTimers {
+    pub cooldown: Timer,
+    pub windup: Timer,
+}
+
+impl DefenderTimers {
+    /// The timers of a defender that has to rest before its first attack.
+    pub fn new(config: &DefenderConfig) -> Self {
+        Self {
+            cooldown: Timer::from_seconds(config.cooldown_secs, false),
+            windup: Timer::from_seconds(config.windup_secs, false),
+        }
+    }
+}
+
+/// Picks targets for rested defenders, closes in on them, and attacks once wound up and in
+/// reach.
+fn defend(
+    time: Res<Time>,
+    config: Res<DefenderConfig>,
+    mut defender_query: Query<
+        (
+            Entity,
+            &Slime,
+            &Transform,
+            &DragActive,
+            &mut Defender,
+            &mut DefenderTimers,
+            &mut Velocity,
+        ),
+        Without<Webbed>,
+    >,
+    spider_query: Query<(Entity, &Spider, &Transform)>,
+    mut events: EventWriter<CombineEvent>,
+) {
+    if !config.enabled {
+        return;
+    }
+    for (entity, slime, transform, drag_active, mut defender, mut timers, mut velocity) in
+        &mut defender_query
+    {
+        if drag_active.0 || slime.size < config.min_size {
+            if defender.engaging.is_some() {
+                defender.engaging = None;
+            }
+            continue;
+        }
+        timers.cooldown.tick(time.delta());
+        let position = transform.translation.truncate();
+        let Some(target) = defender.engaging else {
+            if !timers.cooldown.finished() {
+                continue;
+            }
+            let nearest = spider_query
+                .iter()
+                .filter(|(_, spider, _)| spider.weakness == slime.color)
+                .map(|(target, _, spider_transform)| {
+                    (
+                        target,
+                        spider_transform.translation.truncate().distance(position),
+                    )
+                })
+                .filter(|&(_, distance)| distance <= config.range_px)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((target, _)) = nearest {
+                timers.windup.reset();
+                defender.engaging = Some(target);
+            }
+            continue;
+        };
+        let Ok((_, spider, spider_transform)) = spider_query.get(target) else {
+            defender.engaging = None;
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        };
+        let spider_position = spider_transform.translation.truncate();
+        let distance = spider_position.distance(position);
+        // let a spider that got away go, rather than chase it across the field.
+        if distance > config.range_px * 1.5 {
+            defender.engaging = None;
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        }
+        let reach = slime.radius_px() + (1. + spider.level as f32) * SPIDER_RADIUS_PX + 10.;
+        timers.windup.tick(time.delta());
+        if timers.windup.finished() && distance <= reach {
+            events.send(CombineEvent {
+                location: spider_position,
+                base: target,
+                addition: entity,
+            });
+            timers.cooldown.reset();
+            defender.engaging = None;
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        }
+        velocity.linvel = (spider_position - position).normalize_or_zero() * config.speed;
+    }
+}
+
+/// Outlines defenders in their own color while they go after a spider.
+fn telegraph_defenders(
+    defender_query: Query<(&Slime, &Defender, &Children), Changed<Defender>>,
+    mut circle_query: Query<&mut DrawMode, With<ActivationCircle>>,
+) {
+    for (slime, defender, children) in &defender_query {
+        let outline = if defender.engaging.is_some() {
+            slime.color.color()
+        } else {
+            Color::NONE
+        };
+        for &child in children.iter() {
+            if let Ok(mut draw_mode) = circle_query.get_mut(child) {
+                if let DrawMode::Outlined {
+                    ref mut outline_mode,
+                    ..
+                } = *draw_mode
+                {
+                    outline_mode.color = outline;
+                }
+            }
+        }
+    }
+}
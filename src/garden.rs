@@ -0,0 +1,302 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+use crate::{
+    assets::FontResources,
+    camera::PlayfieldAnchor,
+    score::ScoreResource,
+    spider::{Spider, SPIDER_RADIUS_PX},
+    waves::WaveConfig,
+    AppState, GameplaySystem, GARDEN_X, MAIN_LAYER, PLAYFIELD_HEIGHT,
+};
+
+/// How many plants the garden has.
+pub const PLANT_COUNT: usize = 5;
+/// How many hit points a plant has when fully grown.
+pub const PLANT_MAX_HP: u32 = 4;
+/// How many hit points the garden starts a run with, across all its plants.
+pub const GARDEN_MAX_HP: u32 = PLANT_COUNT as u32 * PLANT_MAX_HP;
+/// How many hit points a plant loses per level of a spider that eats it.
+pub const GARDEN_DAMAGE_PER_LEVEL: u32 = 1;
+/// Hit points every plant grows back whenever a new wave starts, eaten ones included.
+pub const PLANT_REGROW_HP: u32 = 1;
+/// Plants stand in the soil column the background paints next to the field.
+pub const PLANT_X: f32 = GARDEN_X - 32.;
+pub const PLANT_RADIUS_PX: f32 = 20.;
+
+/// The plants the slimes defend, which spiders make for and eat. The run ends once every plant
+/// has been eaten, and they grow back a little with every wave until then.
+pub struct GardenPlugin;
+
+impl Plugin for GardenPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Garden::default())
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(reset_garden)
+                    .with_system(spawn_plants)
+                    .with_system(setup_garden_text),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(spiders_eat_plants.after(GameplaySystem::Abilities))
+                    .with_system(regrow_plants.after(spiders_eat_plants))
+                    .with_system(tally_garden.after(regrow_plants))
+                    .with_system(show_plants.after(tally_garden))
+                    .with_system(show_garden_health.after(tally_garden)),
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::GameOver).with_system(despawn_garden_text),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::GameOver).with_system(despawn_plants));
+    }
+}
+
+/// The garden's health over the current run, which is its plants' put together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Garden {
+    pub hp: u32,
+    pub max_hp: u32,
+    /// The last spider that ate a plant, which is the one that ended the run once `hp` is 0.
+    pub last_breach: Option<Spider>,
+    /// The wave the plants last grew back for.
+    pub wave: usize,
+}
+
+impl Default for Garden {
+    fn default() -> Self {
+        Self {
+            hp: GARDEN_MAX_HP,
+            max_hp: GARDEN_MAX_HP,
+            last_breach: None,
+            wave: 0,
+        }
+    }
+}
+
+/// One of the garden's plants, which is eaten once it has no hit points left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Plant {
+    pub hp: u32,
+}
+
+impl Plant {
+    pub fn is_eaten(&self) -> bool {
+        self.hp == 0
+    }
+
+    /// Takes up to `appetite` hit points off the plant, and returns how much of it is left.
+    pub fn eat(&mut self, appetite: u32) -> u32 {
+        let eaten = appetite.min(self.hp);
+        self.hp -= eaten;
+        appetite - eaten
+    }
+
+    /// Grows `hp` back, up to [`PLANT_MAX_HP`].
+    pub fn grow(&mut self, hp: u32) {
+        self.hp = (self.hp + hp).min(PLANT_MAX_HP);
+    }
+}
+
+/// Where the `index`th plant stands, spread evenly down the soil.
+pub fn plant_position(index: usize) -> Vec2 {
+    let spacing = PLAYFIELD_HEIGHT / PLANT_COUNT as f32;
+    Vec2::new(
+        PLANT_X,
+        PLAYFIELD_HEIGHT / 2. - spacing * (index as f32 + 0.5),
+    )
+}
+
+/// The uneaten plant closest to `position`, out of `plants`.
+pub fn nearest_plant(
+    position: Vec2,
+    plants: impl IntoIterator<Item = (Entity, Vec2)>,
+) -> Option<(Entity, Vec2)> {
+    plants.into_iter().min_by(|a, b| {
+        a.1.distance_squared(position)
+            .total_cmp(&b.1.distance_squared(position))
+    })
+}
+
+/// How many hit points `spider` takes off a plant when it eats it.
+pub fn garden_damage(spider: &Spider) -> u32 {
+    spider.level * GARDEN_DAMAGE_PER_LEVEL
+}
+
+/// The "GARDEN hp/max" readout in the corner of the screen.
+#[derive(Component)]
+pub struct GardenText;
+
+fn reset_garden(mut garden: ResMut<Garden>) {
+    *garden = Garden::default();
+}
+
+fn spawn_plants(mut commands: Commands) {
+    for index in 0..PLANT_COUNT {
+        commands
+            .spawn_bundle(GeometryBuilder::build_as(
+                &shapes::Circle {
+                    radius: PLANT_RADIUS_PX,
+                    center: Vec2::ZERO,
+                },
+                DrawMode::Outlined {
+                    fill_mode: FillMode::color(Color::NONE),
+                    outline_mode: StrokeMode::new(Color::NONE, 4.0),
+                },
+                Transform::from_translation(plant_position(index).extend(MAIN_LAYER)),
+            ))
+            .insert(Plant { hp: PLANT_MAX_HP });
+    }
+}
+
+/// Lets every spider that gets to an uneaten plant eat its [`garden_damage`] and despawns it,
+/// then ends the run if that was the last of the garden.
+///
+/// A spider that finishes off a plant and is still hungry moves on to the next nearest. One that
+/// gets past the plants without touching any eats the nearest anyway.
+fn spiders_eat_plants(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    mut garden: ResMut<Garden>,
+    spider_query: Query<(Entity, &Transform, &Spider)>,
+    mut plant_query: Query<(Entity, &Transform, &mut Plant), Without<Spider>>,
+) {
+    for (entity, transform, spider) in &spider_query {
+        let position = transform.translation.truncate();
+        let uneaten = plant_query
+            .iter()
+            .filter(|(_, _, plant)| !plant.is_eaten())
+            .map(|(plant, transform, _)| (plant, transform.translation.truncate()));
+        let Some((plant, plant_position)) = nearest_plant(position, uneaten) else {
+            continue;
+        };
+        let reach = PLANT_RADIUS_PX + (1. + spider.level as f32) * SPIDER_RADIUS_PX;
+        if position.distance(plant_position) > reach && position.x > PLANT_X {
+            continue;
+        }
+        let mut appetite = plant_query
+            .get_mut(plant)
+            .unwrap()
+            .2
+            .eat(garden_damage(spider));
+        while appetite > 0 {
+            let uneaten = plant_query
+                .iter()
+                .filter(|(_, _, plant)| !plant.is_eaten())
+                .map(|(plant, transform, _)| (plant, transform.translation.truncate()));
+            let Some((next, _)) = nearest_plant(plant_position, uneaten) else {
+                break;
+            };
+            appetite = plant_query.get_mut(next).unwrap().2.eat(appetite);
+        }
+        garden.last_breach = Some(*spider);
+        commands.entity(entity).despawn_recursive();
+        if plant_query.iter().all(|(_, _, plant)| plant.is_eaten()) {
+            // another system may already have queued a transition this frame, which then wins.
+            let _ = state.set(AppState::GameOver);
+            break;
+        }
+    }
+}
+
+/// Grows every plant back by [`PLANT_REGROW_HP`] when a new wave starts.
+fn regrow_plants(
+    waves: Res<WaveConfig>,
+    score: Res<ScoreResource>,
+    mut garden: ResMut<Garden>,
+    mut plant_query: Query<&mut Plant>,
+) {
+    let wave = waves.wave(score.spiders_spawned);
+    if wave == garden.wave {
+        return;
+    }
+    // the first wave starts with the garden fully grown.
+    if garden.wave != 0 {
+        for mut plant in &mut plant_query {
+            plant.grow(PLANT_REGROW_HP);
+        }
+    }
+    garden.wave = wave;
+}
+
+fn tally_garden(mut garden: ResMut<Garden>, plant_query: Query<&Plant>) {
+    garden.hp = plant_query.iter().map(|plant| plant.hp).sum();
+}
+
+/// Draws plants in green that fades as they're eaten, and eaten ones as bare soil.
+fn show_plants(mut plant_query: Query<(&Plant, &mut DrawMode), Changed<Plant>>) {
+    for (plant, mut draw_mode) in &mut plant_query {
+        let (fill, outline) = if plant.is_eaten() {
+            (Color::NONE, Color::rgba(0.35, 0.2, 0.1, 0.8))
+        } else {
+            let grown = plant.hp as f32 / PLANT_MAX_HP as f32;
+            (
+                Color::rgba(0.3, 0.75, 0.25, 0.4 + 0.6 * grown),
+                Color::rgb(0.1, 0.4, 0.1),
+            )
+        };
+        *draw_mode = DrawMode::Outlined {
+            fill_mode: FillMode::color(fill),
+            outline_mode: StrokeMode::new(outline, 4.0),
+        };
+    }
+}
+
+fn setup_garden_text(mut commands: Commands, fonts: Res<FontResources>) {
+    commands
+        .spawn_bundle(
+            TextBundle::from_sections([
+                TextSection {
+                    value: "GARDEN ".to_owned(),
+                    style: TextStyle {
+                        font: fonts.game.clone(),
+                        font_size: 24.,
+                        color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+                    },
+                },
+                TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        font: fonts.game.clone(),
+                        font_size: 32.,
+                        color: Color::WHITE,
+                    },
+                },
+            ])
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Px(16.0),
+                    top: Val::Px(8.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(PlayfieldAnchor::at(16.0, 8.0))
+        .insert(GardenText);
+}
+
+/// Keeps the readout up to date.
+fn show_garden_health(garden: Res<Garden>, mut text_query: Query<&mut Text, With<GardenText>>) {
+    for mut text in &mut text_query {
+        text.sections[1].value = format!("{}/{}", garden.hp, garden.max_hp);
+        text.sections[1].style.color = if garden.hp * 4 <= garden.max_hp {
+            Color::RED
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+fn despawn_garden_text(mut commands: Commands, query: Query<Entity, With<GardenText>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn despawn_plants(mut commands: Commands, query: Query<Entity, With<Plant>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
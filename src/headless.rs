@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use bevy::{
+    hierarchy::HierarchyPlugin,
+    input::InputPlugin,
+    prelude::*,
+    time::{create_time_channels, TimeSender},
+    transform::TransformPlugin,
+};
+
+use crate::{
+    assets::{FontResources, SlimeResources, SpiderResources, TileResources},
+    rng::RngSeed,
+    slime::SlimeColor,
+    AppState, GameplayPlugin,
+};
+
+/// Runs the in-game rules without a window, renderer or asset server.
+///
+/// Add this after `MinimalPlugins`. The game starts directly in [`AppState::InGame`], and
+/// [`Time`] advances by [`HeadlessTimestep`] on every update instead of following the wall
+/// clock, so a run plays out the same no matter how fast the host steps it. The timestep starts
+/// out as `timestep`, unless a [`HeadlessTimestep`] was inserted before adding the plugin.
+pub struct HeadlessPlugin {
+    pub timestep: Duration,
+}
+
+impl Default for HeadlessPlugin {
+    fn default() -> Self {
+        Self {
+            timestep: Duration::from_secs_f64(1. / 60.),
+        }
+    }
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        // `time_system` reads the next instant from this channel when it is present; it is
+        // meant for the render world, but nothing else is going to use it here.
+        let (time_sender, time_receiver) = create_time_channels();
+        let timestep = app
+            .world
+            .get_resource::<HeadlessTimestep>()
+            .map_or(self.timestep, |timestep| timestep.0);
+        // start the clock now, and queue up the first step so that the very first update
+        // already moves time forward.
+        let mut time = app.world.resource_mut::<Time>();
+        let startup = time.startup();
+        time.update_with_instant(startup);
+        time_sender
+            .0
+            .send(startup + timestep)
+            .expect("the receiver is alive");
+        app.add_plugin(TransformPlugin)
+            .add_plugin(HierarchyPlugin)
+            .add_plugin(InputPlugin)
+            .insert_resource(time_receiver)
+            .insert_resource(time_sender)
+            .insert_resource(HeadlessTimestep(timestep))
+            .add_system_to_stage(CoreStage::Last, advance_time.label(AdvanceTime))
+            // the spawners only need handles, which never have to point at anything.
+            .insert_resource(SlimeResources {
+                texture_atlases: SlimeColor::ALL
+                    .iter()
+                    .map(|&color| (color, Handle::default()))
+                    .collect(),
+            })
+            .init_resource::<SpiderResources>()
+            .init_resource::<TileResources>()
+            .init_resource::<FontResources>()
+            .add_state(AppState::InGame)
+            .add_plugin(GameplayPlugin);
+    }
+}
+
+/// Builds an app with `MinimalPlugins` and a default [`HeadlessPlugin`], seeded with `seed`.
+pub fn headless_app(seed: u64) -> App {
+    let mut app = App::new();
+    app.insert_resource(RngSeed(seed))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(HeadlessPlugin::default());
+    app
+}
+
+/// How far [`Time`] moves on the next update.
+pub struct HeadlessTimestep(pub Duration);
+
+/// Sends the instant of the next update to `time_system`. Anything that changes
+/// [`HeadlessTimestep`] has to run before this.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SystemLabel)]
+pub struct AdvanceTime;
+
+fn advance_time(time: Res<Time>, timestep: Res<HeadlessTimestep>, sender: Res<TimeSender>) {
+    let now = time
+        .last_update()
+        .expect("the clock is started when the plugin is built");
+    sender
+        .0
+        .try_send(now + timestep.0)
+        .expect("time channel is full");
+}
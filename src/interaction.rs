@@ -0,0 +1,326 @@
+use bevy::{input::InputSystem, prelude::*};
+use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*};
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    camera::MainCamera,
+    shop::{ShopSlot, SHOP_SLOT_RADIUS_PX},
+    spider::Webbed,
+    AppState, GameplaySystem, DRAG_LAYER, MAIN_LAYER, PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH,
+    SHAPE_LAYER,
+};
+
+/// Hovering, dragging and dropping slimes, driven by [`MousePosition`] and
+/// `Input<MouseButton>`.
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MousePosition(None))
+            .add_event::<CombineEvent>()
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    // runs ahead of anything that might despawn the new interactable this frame.
+                    .with_system(add_activation_circle.before(GameplaySystem::Combine))
+                    .with_system(resize_activation_circles.after(GameplaySystem::Combine))
+                    .with_system(color_on_hover),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .label(GameplaySystem::Drag)
+                    .after(GameplaySystem::Movement)
+                    .with_system(drag_start)
+                    .with_system(drag_update.after(drag_start))
+                    // drops go to whatever is under the cursor on the frame of release.
+                    .with_system(mouse_hover.after(drag_update))
+                    .with_system(drag_end.after(mouse_hover)),
+            )
+            .add_system_set(SystemSet::on_enter(AppState::GameOver).with_system(remove_all_hover));
+    }
+}
+
+/// Feeds the primary window's cursor into [`MousePosition`] and keeps the cursor icon in sync
+/// with what is being hovered or dragged.
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        // update the position before anything in `CoreStage::Update` reads it.
+        app.add_system_to_stage(CoreStage::PreUpdate, sync_mouse_position.after(InputSystem))
+            .add_system_set(SystemSet::on_update(AppState::InGame).with_system(set_mouse_icon))
+            .add_system_set(SystemSet::on_enter(AppState::GameOver).with_system(reset_cursor_icon));
+    }
+}
+
+#[derive(Default)]
+pub struct MousePosition(pub Option<Vec2>);
+
+#[derive(Component)]
+pub struct Interactable {
+    pub activation_radius: f32,
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct DragActive(pub bool);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct HoverActive(pub bool);
+
+#[derive(Component)]
+pub struct ActivationCircle;
+
+pub struct CombineEvent {
+    pub location: Vec2,
+    pub base: Entity,
+    pub addition: Entity,
+}
+
+fn set_mouse_icon(
+    mut windows: ResMut<Windows>,
+    drag_query: Query<&DragActive>,
+    hover_query: Query<&HoverActive, With<DragActive>>,
+) {
+    let window = windows.primary_mut();
+    for drag_active in &drag_query {
+        if drag_active.0 {
+            window.set_cursor_icon(CursorIcon::Grabbing);
+            return;
+        }
+    }
+    for hover_active in &hover_query {
+        if hover_active.0 {
+            window.set_cursor_icon(CursorIcon::Grab);
+            return;
+        }
+    }
+    window.set_cursor_icon(CursorIcon::Default);
+}
+
+fn mouse_hover(
+    mouse_position: Res<MousePosition>,
+    mut interactable: Query<(
+        &Transform,
+        &Interactable,
+        Option<&DragActive>,
+        &mut HoverActive,
+    )>,
+) {
+    if let Some(mouse_pos) = mouse_position.0 {
+        for (transform, interactable, drag_active, mut hover_active) in interactable.iter_mut() {
+            if transform.translation.truncate().distance(mouse_pos) < interactable.activation_radius
+                && !drag_active.map(|x| x.0).unwrap_or(false)
+            {
+                if !hover_active.0 {
+                    hover_active.0 = true;
+                }
+            } else {
+                if hover_active.0 {
+                    hover_active.0 = false;
+                }
+            }
+        }
+    }
+}
+
+fn color_on_hover(
+    hover_query: Query<(&HoverActive, &Children), Changed<HoverActive>>,
+    mut circle_query: Query<&mut DrawMode, With<ActivationCircle>>,
+) {
+    for (hover_active, children) in hover_query.iter() {
+        for &child in children.iter() {
+            if let Ok(DrawMode::Outlined {
+                ref mut fill_mode, ..
+            }) = circle_query.get_mut(child).as_deref_mut()
+            {
+                *fill_mode = if hover_active.0 {
+                    bevy_prototype_lyon::prelude::FillMode::color(Color::rgba(0.5, 0.5, 0.5, 0.5))
+                } else {
+                    bevy_prototype_lyon::prelude::FillMode::color(Color::NONE)
+                }
+            }
+        }
+    }
+}
+
+fn add_activation_circle(
+    mut commands: Commands,
+    interactable_query: Query<(Entity, &Interactable), Added<Interactable>>,
+) {
+    for (entity, interactable) in &interactable_query {
+        let circle_entity = commands
+            .spawn_bundle(circle_shape(interactable.activation_radius, Color::NONE))
+            .insert(ActivationCircle)
+            .id();
+        commands.entity(entity).add_child(circle_entity);
+    }
+}
+
+/// An unfilled circle of `radius` around its parent, outlined in `outline`.
+pub fn circle_shape(radius: f32, outline: Color) -> ShapeBundle {
+    let shape = shapes::Circle {
+        radius,
+        ..default()
+    };
+    GeometryBuilder::build_as(
+        &shape,
+        DrawMode::Outlined {
+            fill_mode: bevy_prototype_lyon::prelude::FillMode::color(Color::NONE),
+            outline_mode: StrokeMode::new(outline, 3.0),
+        },
+        Transform::from_xyz(0., 0., SHAPE_LAYER),
+    )
+}
+
+/// The outline of a [`circle_shape`] of `radius`, for circles that change size.
+pub fn circle_path(radius: f32) -> Path {
+    ShapePath::build_as(&shapes::Circle {
+        radius,
+        ..default()
+    })
+}
+
+/// Redraws the activation circles of interactables whose reach changed.
+fn resize_activation_circles(
+    interactable_query: Query<(&Interactable, &Children), Changed<Interactable>>,
+    mut circle_query: Query<&mut Path, With<ActivationCircle>>,
+) {
+    for (interactable, children) in &interactable_query {
+        for &child in children.iter() {
+            if let Ok(mut path) = circle_query.get_mut(child) {
+                *path = circle_path(interactable.activation_radius);
+            }
+        }
+    }
+}
+
+fn drag_start(
+    mouse_input: Res<Input<MouseButton>>,
+    mouse_position: Res<MousePosition>,
+    // audio: Res<Audio>,
+    // audio_resources: Res<AudioResources>,
+    mut draggable_query: Query<
+        (
+            &mut Transform,
+            &Interactable,
+            &mut DragActive,
+            &mut HoverActive,
+            &mut CollisionGroups,
+        ),
+        Without<Webbed>,
+    >,
+    slot_query: Query<&Transform, (With<ShopSlot>, Without<Interactable>)>,
+) {
+    if mouse_input.just_pressed(MouseButton::Left) {
+        let mouse_pos = mouse_position.0.unwrap();
+        // a click on the shop buys a slime rather than picking up whatever is under it.
+        if slot_query.iter().any(|transform| {
+            transform.translation.truncate().distance(mouse_pos) < SHOP_SLOT_RADIUS_PX
+        }) {
+            return;
+        }
+        for (mut transform, draggable, mut drag_active, mut hover_active, mut collision_groups) in
+            &mut draggable_query
+        {
+            if transform.translation.truncate().distance(mouse_pos) < draggable.activation_radius {
+                // audio.play(audio_resources.grab.clone());
+                drag_active.0 = true;
+                hover_active.0 = false;
+                transform.translation.z = DRAG_LAYER;
+                collision_groups.filters = 0;
+                // only drag one thing at a time.
+                break;
+            }
+        }
+    }
+}
+
+fn drag_update(
+    mouse_position: Res<MousePosition>,
+    mut draggable_query: Query<(&DragActive, &mut Transform), With<Interactable>>,
+) {
+    if let Some(mouse_coords) = mouse_position.0 {
+        for (drag_active, mut transform) in &mut draggable_query {
+            if drag_active.0 {
+                transform.translation.x = mouse_coords.x;
+                transform.translation.y = mouse_coords.y;
+            }
+        }
+    }
+}
+
+fn drag_end(
+    mouse_position: Res<MousePosition>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut drag_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut DragActive,
+        &mut CollisionGroups,
+        &mut Velocity,
+    )>,
+    hover_query: Query<(Entity, &HoverActive)>,
+    mut events: EventWriter<CombineEvent>,
+) {
+    if mouse_input.just_released(MouseButton::Left) {
+        let mut addition_entity: Option<Entity> = None;
+        let mut base_entity: Option<Entity> = None;
+        for (entity, mut transform, mut drag_active, mut collision_groups, mut velocity) in
+            &mut drag_query
+        {
+            if drag_active.0 {
+                drag_active.0 = false;
+                transform.translation.z = MAIN_LAYER;
+                collision_groups.filters = !0;
+                *velocity = Velocity::zero();
+                addition_entity = Some(entity);
+                break;
+            }
+        }
+        for (entity, hover_active) in &hover_query {
+            if hover_active.0 {
+                base_entity = Some(entity);
+                break;
+            }
+        }
+        if let (Some(addition), Some(base)) = (addition_entity, base_entity) {
+            events.send(CombineEvent {
+                base,
+                addition,
+                location: mouse_position.0.unwrap(),
+            })
+        }
+    }
+}
+
+fn remove_all_hover(mut query: Query<&mut DrawMode, With<ActivationCircle>>) {
+    for mut draw_mode in &mut query {
+        *draw_mode = DrawMode::Fill(bevy_prototype_lyon::prelude::FillMode::color(Color::NONE));
+    }
+}
+
+fn reset_cursor_icon(mut windows: ResMut<Windows>) {
+    let window = windows.primary_mut();
+    window.set_cursor_icon(CursorIcon::Default);
+}
+
+fn sync_mouse_position(
+    windows: Res<Windows>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut mouse_position: ResMut<MousePosition>,
+) {
+    // taken from https://bevy-cheatbook.github.io/cookbook/cursor2world.html
+    let (camera, camera_transform) = camera_query.single();
+    let window = windows.get_primary().unwrap();
+    if let Some(screen_pos) = window.cursor_position() {
+        let window_size = Vec2::new(window.width(), window.height());
+        let ndc = (screen_pos / window_size) * 2.0 - Vec2::ONE;
+        let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix().inverse();
+        let world_pos = ndc_to_world.project_point3(ndc.extend(-1.0));
+        // the cursor can be over the letterbox bars, but nothing should be dragged behind them.
+        let half_playfield = Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT) / 2.;
+        let world_pos: Vec2 = world_pos.truncate().clamp(-half_playfield, half_playfield);
+        mouse_position.0 = Some(world_pos);
+    } else {
+        mouse_position.0 = None;
+    }
+}
@@ -0,0 +1,145 @@
+#![allow(clippy::type_complexity)]
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::ShapePlugin;
+
+pub mod ability;
+pub mod animation;
+pub mod assets;
+pub mod background;
+pub mod boss;
+pub mod camera;
+pub mod combine;
+pub mod defender;
+pub mod garden;
+pub mod headless;
+pub mod interaction;
+pub mod loot;
+pub mod menu;
+pub mod pathing;
+pub mod physics;
+pub mod replay;
+pub mod rng;
+pub mod scenario;
+pub mod score;
+pub mod shop;
+pub mod simulation;
+pub mod slime;
+pub mod spider;
+pub mod splash;
+pub mod terrain;
+pub mod waves;
+
+use ability::AbilityPlugin;
+use animation::AnimationPlugin;
+use assets::AssetsPlugin;
+use background::BackgroundPlugin;
+use boss::BossPlugin;
+use camera::CameraPlugin;
+use combine::CombinePlugin;
+use defender::DefenderPlugin;
+use garden::GardenPlugin;
+use interaction::{CursorPlugin, InteractionPlugin};
+use loot::LootPlugin;
+use menu::MenuPlugin;
+use pathing::PathingPlugin;
+use physics::PhysicsPlugin;
+use rng::RngPlugin;
+use score::ScorePlugin;
+use shop::ShopPlugin;
+use slime::SlimePlugin;
+use spider::SpiderPlugin;
+use splash::SplashPlugin;
+use terrain::TerrainPlugin;
+use waves::WavesPlugin;
+
+/// The size of the playfield in world units, which is also its logical resolution: the camera
+/// scales it to fit the window, whatever size that is.
+pub const PLAYFIELD_WIDTH: f32 = 1280.;
+pub const PLAYFIELD_HEIGHT: f32 = 720.;
+
+pub const PIXELS_PER_METER: f32 = 30.;
+
+pub const TERRAIN_LAYER: f32 = 1.5;
+pub const MAIN_LAYER: f32 = 2.;
+pub const DRAG_LAYER: f32 = 5.;
+pub const SHAPE_LAYER: f32 = 7.;
+pub const LABEL_LAYER: f32 = 8.;
+
+pub const GARDEN_X: f32 = -PLAYFIELD_WIDTH / 2. + 32. * 5.;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AppState {
+    PreGame,
+    InGame,
+    GameOver,
+}
+
+/// Orders the in-game systems that feed into each other, so that the same seed and the same
+/// inputs always play out the same way.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SystemLabel)]
+pub enum GameplaySystem {
+    /// Reseeds [`rng::GameRng`] when a run starts.
+    StartRun,
+    /// Slimes wandering around on their own.
+    Movement,
+    /// Picking up, dragging and dropping with the mouse.
+    Drag,
+    /// Slimes attacking spiders on their own.
+    Defend,
+    /// Resolving drops into merges and attacks.
+    Combine,
+    /// Spiders picking their velocity for the frame.
+    Steering,
+    /// Slime abilities acting on the spiders and slimes around them.
+    Abilities,
+}
+
+/// The whole game, minus the window and renderer.
+///
+/// Add this after `DefaultPlugins`. Every sub-plugin is public, so an embedding app can
+/// add them one by one instead and leave out or replace the pieces it doesn't want.
+pub struct SlimesVsSpidersPlugin;
+
+impl Plugin for SlimesVsSpidersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state(AppState::PreGame)
+            .add_plugin(ShapePlugin)
+            .add_plugin(CameraPlugin)
+            .add_plugin(AssetsPlugin)
+            .add_plugin(BackgroundPlugin)
+            .add_plugin(CursorPlugin)
+            .add_plugin(WavesPlugin)
+            .add_plugin(MenuPlugin)
+            .add_plugin(GameplayPlugin);
+    }
+}
+
+/// The in-game rules: slimes, spiders, dragging, physics, score and the random streams they
+/// draw from.
+///
+/// None of these systems touch a window or load assets, so this is also what
+/// [`headless::HeadlessPlugin`] runs.
+pub struct GameplayPlugin;
+
+impl Plugin for GameplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(RngPlugin)
+            .add_plugin(PhysicsPlugin)
+            .add_plugin(AnimationPlugin)
+            .add_plugin(InteractionPlugin)
+            .add_plugin(CombinePlugin)
+            .add_plugin(ScorePlugin)
+            .add_plugin(SlimePlugin)
+            .add_plugin(SpiderPlugin)
+            .add_plugin(PathingPlugin)
+            .add_plugin(BossPlugin)
+            .add_plugin(GardenPlugin)
+            .add_plugin(AbilityPlugin)
+            .add_plugin(DefenderPlugin)
+            .add_plugin(ShopPlugin)
+            .add_plugin(LootPlugin)
+            .add_plugin(TerrainPlugin)
+            .add_plugin(SplashPlugin);
+    }
+}
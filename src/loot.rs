@@ -0,0 +1,416 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+use rand::Rng;
+
+use crate::{
+    assets::FontResources,
+    camera::PlayfieldAnchor,
+    combine::SpiderKilledEvent,
+    interaction::{circle_shape, DragActive, Interactable},
+    rng::{GameRng, RngStream},
+    slime::{Slime, SlimeColor},
+    spider::Spider,
+    AppState, GameplaySystem, LABEL_LAYER, MAIN_LAYER,
+};
+
+/// How likely a spider is to drop a pickup when it's killed.
+pub const LOOT_CHANCE: f64 = 0.3;
+/// Seconds a pickup lies around before it's gone.
+pub const PICKUP_LIFETIME_SECS: f32 = 10.;
+pub const PICKUP_RADIUS_PX: f32 = 16.;
+pub const POTION_DURATION_SECS: f32 = 15.;
+pub const SIZE_UP_DURATION_SECS: f32 = 15.;
+pub const FREEZE_DURATION_SECS: f32 = 4.;
+pub const RAINBOW_DURATION_SECS: f32 = 10.;
+
+/// Pickups that killed spiders sometimes leave behind, and the power-ups slimes get by being
+/// dragged over them.
+///
+/// A slime holds one power-up at a time, and attacks as the power-up says until it runs out.
+/// [`combine`](crate::combine) asks [`empowered`] what that makes the slime, but only for the
+/// attack: whatever is left of the slime afterwards is back to its plain self.
+pub struct LootPlugin;
+
+impl Plugin for LootPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_enter(AppState::InGame)
+                .with_system(thaw_field)
+                .with_system(setup_power_up_text),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(drop_loot.after(GameplaySystem::Combine))
+                .with_system(
+                    collect_pickups
+                        .after(GameplaySystem::Drag)
+                        .before(GameplaySystem::Combine),
+                )
+                .with_system(expire_pickups.after(drop_loot).after(collect_pickups))
+                .with_system(wear_off_power_ups.after(GameplaySystem::Combine))
+                .with_system(freeze_field.after(GameplaySystem::Abilities))
+                .with_system(show_power_ups.after(wear_off_power_ups).after(freeze_field))
+                .with_system(move_badges.after(GameplaySystem::Combine)),
+        )
+        .add_system_set(SystemSet::on_enter(AppState::GameOver).with_system(despawn_power_up_text))
+        .add_system_set(SystemSet::on_exit(AppState::GameOver).with_system(despawn_pickups));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpKind {
+    /// The slime attacks as this color.
+    Potion(SlimeColor),
+    /// The slime attacks as one size bigger.
+    SizeUp,
+    /// Every spider on the field stands still.
+    Freeze,
+    /// The slime attacks as whatever color the spider it hits is weak to.
+    Rainbow,
+}
+
+impl PowerUpKind {
+    /// Draws a kind of pickup from `rng`, each as likely as the other, and potions of any color.
+    pub fn random(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..4) {
+            0 => Self::Potion(SlimeColor::ALL[rng.gen_range(0..8)]),
+            1 => Self::SizeUp,
+            2 => Self::Freeze,
+            _ => Self::Rainbow,
+        }
+    }
+
+    pub fn duration_secs(&self) -> f32 {
+        match self {
+            Self::Potion(_) => POTION_DURATION_SECS,
+            Self::SizeUp => SIZE_UP_DURATION_SECS,
+            Self::Freeze => FREEZE_DURATION_SECS,
+            Self::Rainbow => RAINBOW_DURATION_SECS,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::Potion(color) => format!("{} potion", color.name()),
+            Self::SizeUp => "size up".to_owned(),
+            Self::Freeze => "freeze".to_owned(),
+            Self::Rainbow => "rainbow".to_owned(),
+        }
+    }
+
+    /// The short label a pickup lying on the field carries.
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Potion(_) => "POT",
+            Self::SizeUp => "+1",
+            Self::Freeze => "ICE",
+            Self::Rainbow => "ANY",
+        }
+    }
+
+    /// What the pickup and the power-up are drawn in, `t` seconds into the run for rainbows,
+    /// which cycle through every color.
+    pub fn color(&self, t: f32) -> Color {
+        match self {
+            Self::Potion(color) => color.color(),
+            Self::SizeUp => Color::WHITE,
+            Self::Freeze => Color::rgb(0.6, 0.85, 1.),
+            Self::Rainbow => Color::hsl((t * 120.) % 360., 0.9, 0.6),
+        }
+    }
+}
+
+/// Something a killed spider left behind, there for the taking until `lifetime` runs out.
+#[derive(Component)]
+pub struct Pickup {
+    pub kind: PowerUpKind,
+    pub lifetime: Timer,
+}
+
+/// A power-up a slime picked up, in effect until `timer` runs out.
+#[derive(Component)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+    pub timer: Timer,
+}
+
+impl PowerUp {
+    pub fn new(kind: PowerUpKind) -> Self {
+        Self {
+            kind,
+            timer: Timer::from_seconds(kind.duration_secs(), false),
+        }
+    }
+}
+
+/// Every spider holds still until the timer runs out.
+pub struct FieldFreeze(pub Timer);
+
+/// The dot on a slime that shows which power-up it holds.
+#[derive(Component)]
+struct PowerUpBadge;
+
+/// The list of power-ups in effect, under the goo readout.
+#[derive(Component)]
+pub struct PowerUpText;
+
+/// What `slime` attacks a spider weak to `weakness` as with `power_up`.
+pub fn empowered(slime: &Slime, power_up: Option<&PowerUp>, weakness: SlimeColor) -> Slime {
+    match power_up.map(|power_up| power_up.kind) {
+        Some(PowerUpKind::Potion(color)) => Slime { color, ..*slime },
+        Some(PowerUpKind::SizeUp) => Slime {
+            size: slime.size + 1,
+            ..*slime
+        },
+        Some(PowerUpKind::Rainbow) => Slime {
+            color: weakness,
+            ..*slime
+        },
+        Some(PowerUpKind::Freeze) | None => *slime,
+    }
+}
+
+fn drop_loot(
+    mut commands: Commands,
+    time: Res<Time>,
+    fonts: Res<FontResources>,
+    mut game_rng: ResMut<GameRng>,
+    mut events: EventReader<SpiderKilledEvent>,
+) {
+    let rng = game_rng.stream(RngStream::Loot);
+    for ev in events.iter() {
+        if !rng.gen_bool(LOOT_CHANCE) {
+            continue;
+        }
+        let kind = PowerUpKind::random(rng);
+        commands
+            .spawn_bundle(circle_shape(PICKUP_RADIUS_PX, Color::WHITE))
+            .insert(Transform::from_translation(ev.position.extend(MAIN_LAYER)))
+            .insert(Pickup {
+                kind,
+                lifetime: Timer::from_seconds(PICKUP_LIFETIME_SECS, false),
+            })
+            .insert(DrawMode::Outlined {
+                fill_mode: FillMode::color(kind.color(time.seconds_since_startup() as f32)),
+                outline_mode: StrokeMode::new(Color::WHITE, 2.0),
+            })
+            .with_children(|parent| {
+                parent.spawn_bundle(Text2dBundle {
+                    text: Text::from_section(
+                        kind.label(),
+                        TextStyle {
+                            font: fonts.game.clone(),
+                            font_size: 16.,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_alignment(TextAlignment::CENTER),
+                    transform: Transform::from_xyz(0., PICKUP_RADIUS_PX + 8., LABEL_LAYER),
+                    ..default()
+                });
+            });
+    }
+}
+
+/// Hands a pickup's power-up to the slime dragged over it, or freezes the field for a freeze.
+fn collect_pickups(
+    mut commands: Commands,
+    slime_query: Query<(
+        Entity,
+        &Transform,
+        &Interactable,
+        &DragActive,
+        Option<&PowerUp>,
+    )>,
+    pickup_query: Query<(Entity, &Transform, &Pickup)>,
+) {
+    for (slime, transform, interactable, drag_active, power_up) in &slime_query {
+        if !drag_active.0 {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        for (entity, pickup_transform, pickup) in &pickup_query {
+            let reach = interactable.activation_radius + PICKUP_RADIUS_PX;
+            if pickup_transform.translation.truncate().distance(position) > reach {
+                continue;
+            }
+            commands.entity(entity).despawn_recursive();
+            if pickup.kind == PowerUpKind::Freeze {
+                commands.insert_resource(FieldFreeze(Timer::from_seconds(
+                    pickup.kind.duration_secs(),
+                    false,
+                )));
+                continue;
+            }
+            // a new power-up takes the place of the one the slime had.
+            commands.entity(slime).insert(PowerUp::new(pickup.kind));
+            if power_up.is_none() {
+                let radius = interactable.activation_radius;
+                let badge = commands
+                    .spawn_bundle(GeometryBuilder::build_as(
+                        &shapes::Circle {
+                            radius: 8.,
+                            center: Vec2::ZERO,
+                        },
+                        DrawMode::Fill(FillMode::color(Color::WHITE)),
+                        Transform::from_xyz(radius * 0.7, radius * 0.7, LABEL_LAYER),
+                    ))
+                    .insert(PowerUpBadge)
+                    .id();
+                commands.entity(slime).add_child(badge);
+            }
+        }
+        // only one slime is ever dragged.
+        break;
+    }
+}
+
+/// Keeps power-up badges on the edge of slimes that grew.
+fn move_badges(
+    slime_query: Query<(&Interactable, &Children), Changed<Interactable>>,
+    mut badge_query: Query<&mut Transform, With<PowerUpBadge>>,
+) {
+    for (interactable, children) in &slime_query {
+        for &child in children.iter() {
+            if let Ok(mut transform) = badge_query.get_mut(child) {
+                let radius = interactable.activation_radius;
+                transform.translation.x = radius * 0.7;
+                transform.translation.y = radius * 0.7;
+            }
+        }
+    }
+}
+
+fn expire_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pickup_query: Query<(Entity, &mut Pickup)>,
+) {
+    for (entity, mut pickup) in &mut pickup_query {
+        if pickup.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn wear_off_power_ups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut slime_query: Query<(Entity, &mut PowerUp, &Children)>,
+    badge_query: Query<(), With<PowerUpBadge>>,
+) {
+    for (entity, mut power_up, children) in &mut slime_query {
+        if !power_up.timer.tick(time.delta()).finished() {
+            continue;
+        }
+        commands.entity(entity).remove::<PowerUp>();
+        for &child in children.iter() {
+            if badge_query.contains(child) {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Holds every spider still while the field is frozen, on top of however else they moved.
+fn freeze_field(
+    mut commands: Commands,
+    time: Res<Time>,
+    freeze: Option<ResMut<FieldFreeze>>,
+    mut spider_query: Query<&mut Velocity, With<Spider>>,
+) {
+    let Some(mut freeze) = freeze else {
+        return;
+    };
+    for mut velocity in &mut spider_query {
+        velocity.linvel = Vec2::ZERO;
+    }
+    if freeze.0.tick(time.delta()).finished() {
+        commands.remove_resource::<FieldFreeze>();
+    }
+}
+
+fn thaw_field(mut commands: Commands) {
+    commands.remove_resource::<FieldFreeze>();
+}
+
+fn setup_power_up_text(mut commands: Commands, fonts: Res<FontResources>) {
+    commands
+        .spawn_bundle(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: fonts.game.clone(),
+                    font_size: 20.,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Px(16.0),
+                    top: Val::Px(76.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(PlayfieldAnchor::at(16.0, 76.0))
+        .insert(PowerUpText);
+}
+
+/// Lists the power-ups in effect with the seconds they have left, and keeps the badges and
+/// pickups of rainbows cycling.
+fn show_power_ups(
+    time: Res<Time>,
+    freeze: Option<Res<FieldFreeze>>,
+    power_up_query: Query<(&PowerUp, &Children)>,
+    mut badge_query: Query<&mut DrawMode, (With<PowerUpBadge>, Without<Pickup>)>,
+    mut pickup_query: Query<(&Pickup, &mut DrawMode), Without<PowerUpBadge>>,
+    mut text_query: Query<&mut Text, With<PowerUpText>>,
+) {
+    let t = time.seconds_since_startup() as f32;
+    let mut lines = Vec::new();
+    if let Some(freeze) = freeze {
+        lines.push(format!(
+            "FREEZE {:.0}s",
+            freeze.0.duration().as_secs_f32() - freeze.0.elapsed_secs()
+        ));
+    }
+    for (power_up, children) in &power_up_query {
+        let left = power_up.timer.duration().as_secs_f32() - power_up.timer.elapsed_secs();
+        lines.push(format!(
+            "{} {left:.0}s",
+            power_up.kind.name().to_uppercase()
+        ));
+        for &child in children.iter() {
+            if let Ok(mut draw_mode) = badge_query.get_mut(child) {
+                *draw_mode = DrawMode::Fill(FillMode::color(power_up.kind.color(t)));
+            }
+        }
+    }
+    for (pickup, mut draw_mode) in &mut pickup_query {
+        if let DrawMode::Outlined {
+            ref mut fill_mode, ..
+        } = *draw_mode
+        {
+            fill_mode.color = pickup.kind.color(t);
+        }
+    }
+    for mut text in &mut text_query {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+fn despawn_power_up_text(mut commands: Commands, query: Query<Entity, With<PowerUpText>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn despawn_pickups(mut commands: Commands, query: Query<Entity, With<Pickup>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
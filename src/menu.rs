@@ -0,0 +1,250 @@
+use bevy::prelude::*;
+
+use crate::{assets::FontResources, camera::PlayfieldAnchor, score::ScoreResource, AppState};
+
+const BUTTON_COLOR: Color = Color::GRAY;
+const BUTTON_COLOR_HOVER: Color = Color::DARK_GRAY;
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(button_system)
+            .add_system_set(SystemSet::on_enter(AppState::PreGame).with_system(setup_main_menu))
+            .add_system_set(SystemSet::on_exit(AppState::PreGame).with_system(despawn_main_menu))
+            .add_system_set(
+                SystemSet::on_enter(AppState::GameOver).with_system(setup_game_over_menu),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::GameOver).with_system(despawn_game_over_menu),
+            );
+    }
+}
+
+#[derive(Component)]
+pub struct MainMenu;
+
+#[derive(Component)]
+pub struct GameOverMenu;
+
+#[derive(Component)]
+pub struct PlayButton;
+
+const INSTRUCTIONS: [&str; 3] = [
+    "Drag  a  slime  onto  another  slime  to  combine  them.",
+    "Drag  a  slime  onto  a  spider  to  attack  it.",
+    "Defeat  spiders  before  they  reach  the  garden.",
+];
+
+fn setup_main_menu(mut commands: Commands, fonts: Res<FontResources>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::ColumnReverse,
+                ..default()
+            },
+            color: Color::rgba(0.0, 0.0, 0.0, 0.9).into(),
+            ..default()
+        })
+        .insert(PlayfieldAnchor::fill())
+        .insert(MainMenu)
+        .with_children(|parent| {
+            let font = fonts.menu.clone();
+            let sections = INSTRUCTIONS.iter().map(|s| TextSection {
+                value: s.to_string(),
+                style: TextStyle {
+                    font: font.clone(),
+                    font_size: 32.,
+                    color: Color::WHITE,
+                },
+            });
+            parent.spawn_bundle(
+                TextBundle::from_section(
+                    "SLIMES vs SPIDERS",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 64.,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(32.0)),
+                    ..default()
+                }),
+            );
+            for section in sections {
+                parent.spawn_bundle(TextBundle::from_sections([section]).with_style(Style {
+                    margin: UiRect::all(Val::Px(16.0)),
+                    ..default()
+                }));
+            }
+            parent
+                .spawn_bundle(ButtonBundle {
+                    style: Style {
+                        margin: UiRect::all(Val::Px(32.0)),
+                        // horizontally center child text
+                        justify_content: JustifyContent::Center,
+                        // vertically center child text
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    color: Color::GRAY.into(),
+                    ..default()
+                })
+                .insert(PlayButton)
+                .with_children(|parent| {
+                    parent.spawn_bundle(
+                        TextBundle::from_section(
+                            "Click to start",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 32.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::all(Val::Px(16.0)),
+                            ..default()
+                        }),
+                    );
+                });
+        });
+}
+
+fn button_system(
+    mut state: ResMut<State<AppState>>,
+    mut interaction_query: Query<
+        (&Interaction, &mut UiColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Hovered => {
+                *color = BUTTON_COLOR_HOVER.into();
+            }
+            Interaction::None => {
+                *color = BUTTON_COLOR.into();
+            }
+            Interaction::Clicked => {
+                state.set(AppState::InGame).expect("could not set state");
+            }
+        }
+    }
+}
+
+fn despawn_main_menu(mut commands: Commands, main_menu_query: Query<Entity, With<MainMenu>>) {
+    for entity in &main_menu_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn setup_game_over_menu(
+    mut commands: Commands,
+    fonts: Res<FontResources>,
+    score: Res<ScoreResource>,
+) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::ColumnReverse,
+                ..default()
+            },
+            color: Color::rgba(0.0, 0.0, 0.0, 0.9).into(),
+            ..default()
+        })
+        .insert(PlayfieldAnchor::fill())
+        .insert(GameOverMenu)
+        .with_children(|parent| {
+            let font = fonts.menu.clone();
+            let game_over_text = TextSection {
+                value: "Game  over!".to_owned(),
+                style: TextStyle {
+                    font: font.clone(),
+                    font_size: 32.,
+                    color: Color::WHITE,
+                },
+            };
+            let score_text = TextSection {
+                value: format!("Score:  {}", score.points),
+                style: TextStyle {
+                    font: font.clone(),
+                    font_size: 48.,
+                    color: Color::WHITE,
+                },
+            };
+            for section in [game_over_text, score_text] {
+                parent.spawn_bundle(TextBundle::from_sections([section]).with_style(Style {
+                    margin: UiRect::all(Val::Px(16.0)),
+                    ..default()
+                }));
+            }
+            let breakdown = [
+                format!("Spiders  defeated:  {}", score.spiders_killed),
+                format!("Best  streak:  {}", score.best_streak),
+                format!("Close  calls:  {}", score.close_calls),
+                format!("Time  survived:  {:.0}s", score.seconds_survived),
+            ];
+            for line in breakdown {
+                parent.spawn_bundle(
+                    TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 24.,
+                            color: Color::rgba(1.0, 1.0, 1.0, 0.7),
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    }),
+                );
+            }
+            parent
+                .spawn_bundle(ButtonBundle {
+                    style: Style {
+                        margin: UiRect::all(Val::Px(16.0)),
+                        // horizontally center child text
+                        justify_content: JustifyContent::Center,
+                        // vertically center child text
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    color: BUTTON_COLOR.into(),
+                    ..default()
+                })
+                .insert(PlayButton)
+                .with_children(|parent| {
+                    parent.spawn_bundle(
+                        TextBundle::from_section(
+                            "Click  to  play  again",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 32.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::all(Val::Px(16.0)),
+                            ..default()
+                        }),
+                    );
+                });
+        });
+}
+
+fn despawn_game_over_menu(
+    mut commands: Commands,
+    game_over_menu_query: Query<Entity, With<GameOverMenu>>,
+) {
+    for entity in &game_over_menu_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
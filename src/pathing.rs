@@ -0,0 +1,277 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    garden::{nearest_plant, Plant},
+    slime::Slime,
+    spider::{Spider, SPIDER_RADIUS_PX},
+    terrain::Obstacle,
+    AppState, GameplaySystem, GARDEN_X, PLAYFIELD_HEIGHT,
+};
+
+/// How far out from the garden spiders stop following their [`SpiderMovement`] and make
+/// straight for the nearest plant, in pixels.
+pub const PLANT_SCENT_PX: f32 = 200.;
+/// How far ahead of an obstacle in its way a spider starts going around it, in pixels.
+pub const DETOUR_LOOKAHEAD_PX: f32 = 24.;
+
+/// Steers spiders towards the garden, each according to its [`SpiderMovement`], and then at the
+/// plant nearest to them, going around obstacles on the way.
+pub struct PathingPlugin;
+
+impl Plugin for PathingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::InGame).with_system(
+                steer_spiders
+                    .label(GameplaySystem::Steering)
+                    .after(GameplaySystem::Combine),
+            ),
+        );
+    }
+}
+
+/// How a spider makes its way across the playfield. Every pattern keeps moving left at the
+/// spider's speed on average; they differ in what happens on the way.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SpiderMovement {
+    /// Straight left.
+    #[default]
+    Straight,
+    /// Up and down in straight lines, `amplitude` pixels either side of where it started, once
+    /// every `period` seconds.
+    ZigZag { amplitude: f32, period: f32 },
+    /// Up and down smoothly, `amplitude` pixels either side of where it started, once every
+    /// `period` seconds.
+    SineWave { amplitude: f32, period: f32 },
+    /// Moves `lane_width` pixels up or down every `interval` seconds, turning around at the
+    /// edges of the field.
+    LaneChange { lane_width: f32, interval: f32 },
+    /// Creeps along at half speed, then rushes at `multiplier` times its speed for `duration`
+    /// seconds out of every `interval`.
+    Charge {
+        multiplier: f32,
+        interval: f32,
+        duration: f32,
+    },
+    /// Sidesteps slimes within `radius` pixels ahead of it.
+    Avoid { radius: f32 },
+}
+
+/// What a spider's movement pattern needs to remember.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Pathing {
+    /// Seconds since the spider spawned.
+    pub elapsed: f32,
+    /// Where the spider spawned, which waves oscillate around.
+    pub origin_y: f32,
+    /// The lane a lane-changing spider is heading for.
+    pub lane_y: f32,
+    /// Which way a lane-changing spider goes next, 1 for up or -1 for down.
+    pub lane_direction: f32,
+    /// How the spider is getting past the obstacle in its way.
+    pub detour: Detour,
+}
+
+impl Pathing {
+    pub fn new(origin_y: f32) -> Self {
+        Self {
+            elapsed: 0.,
+            origin_y,
+            lane_y: origin_y,
+            lane_direction: 1.,
+            detour: Detour::None,
+        }
+    }
+}
+
+/// How a spider is getting past an obstacle that blocks its way, see [`detour`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Detour {
+    /// Nothing is in its way.
+    #[default]
+    None,
+    /// Going around, 1 for up or -1 for down.
+    Around(f32),
+    /// Going around the other way, after running into the edge of the field.
+    Back(f32),
+    /// Walking straight over it, after running into the edge both ways.
+    Trample,
+}
+
+/// How far from the middle a spider may stray, so that it stays on the field.
+fn max_y(level: u32) -> f32 {
+    PLAYFIELD_HEIGHT / 2. - (1. + level as f32) * SPIDER_RADIUS_PX
+}
+
+/// The velocity a spider at `position` wants, `dt` seconds after the last time it was asked.
+///
+/// `slimes` are the positions of every slime, for the patterns that care about them.
+pub fn spider_velocity(
+    spider: &Spider,
+    pathing: &mut Pathing,
+    position: Vec2,
+    slimes: &[Vec2],
+    dt: f32,
+) -> Vec2 {
+    let previous = pathing.elapsed;
+    pathing.elapsed += dt;
+    let t = pathing.elapsed;
+    // the middle of the step, so that waves come out even however long the steps are.
+    let mid = previous + dt / 2.;
+    let speed = spider.speed;
+    let velocity = match spider.movement {
+        SpiderMovement::Straight => Vec2::new(-speed, 0.),
+        SpiderMovement::ZigZag { amplitude, period } => {
+            // a triangle wave starting upwards: up a quarter, down a half, up a quarter.
+            let phase = (mid / period).fract();
+            let direction = if (0.25..0.75).contains(&phase) {
+                -1.
+            } else {
+                1.
+            };
+            Vec2::new(-speed, direction * 4. * amplitude / period)
+        }
+        SpiderMovement::SineWave { amplitude, period } => {
+            let omega = std::f32::consts::TAU / period;
+            Vec2::new(-speed, amplitude * omega * (omega * mid).cos())
+        }
+        SpiderMovement::LaneChange {
+            lane_width,
+            interval,
+        } => {
+            if (previous / interval).floor() != (t / interval).floor() {
+                let limit = max_y(spider.level);
+                let mut next = pathing.lane_y + pathing.lane_direction * lane_width;
+                if next.abs() > limit {
+                    pathing.lane_direction = -pathing.lane_direction;
+                    next = pathing.lane_y + pathing.lane_direction * lane_width;
+                }
+                pathing.lane_y = next.clamp(-limit, limit);
+            }
+            // head for the lane at full speed, without overshooting it.
+            let to_lane = pathing.lane_y - position.y;
+            let vy = if dt > 0. { to_lane / dt } else { 0. };
+            Vec2::new(-speed, vy.clamp(-speed, speed))
+        }
+        SpiderMovement::Charge {
+            multiplier,
+            interval,
+            duration,
+        } => {
+            let charging = t % interval >= interval - duration;
+            Vec2::new(-speed * if charging { multiplier } else { 0.5 }, 0.)
+        }
+        SpiderMovement::Avoid { radius } => {
+            let push: f32 = slimes
+                .iter()
+                .map(|&slime| slime - position)
+                .filter(|offset| offset.x < 0. && offset.length() < radius)
+                .map(|offset| {
+                    // the closer the slime, the harder it pushes; dead ahead counts as below.
+                    let away = if offset.y > 0. { -1. } else { 1. };
+                    away * (1. - offset.length() / radius)
+                })
+                .sum();
+            Vec2::new(-speed, (push * speed).clamp(-speed, speed))
+        }
+    };
+    // keep to the field, which kinematic bodies would otherwise walk straight out of.
+    let limit = max_y(spider.level);
+    if (position.y >= limit && velocity.y > 0.) || (position.y <= -limit && velocity.y < 0.) {
+        Vec2::new(velocity.x, 0.)
+    } else {
+        velocity
+    }
+}
+
+/// Turns a spider of `level` at `position`, about to move at `velocity`, up or down around the
+/// first of `obstacles` that blocks its way left, and slows it down in any it gets through.
+///
+/// It goes around the side it is already on, and keeps going that way until it is clear. If that
+/// runs into the edge of the field it turns around, and if the other way does too it tramples
+/// straight over instead of getting stuck.
+pub fn detour(
+    level: u32,
+    pathing: &mut Pathing,
+    position: Vec2,
+    velocity: Vec2,
+    obstacles: &[Obstacle],
+) -> Vec2 {
+    let radius = (1. + level as f32) * SPIDER_RADIUS_PX;
+    let slowdown = obstacles
+        .iter()
+        .filter(|obstacle| !obstacle.kind.blocks_spiders() && obstacle.overlaps(position, radius))
+        .map(|obstacle| obstacle.kind.slowdown())
+        .fold(1., f32::min);
+    let velocity = velocity * slowdown;
+    let blocking = obstacles.iter().find(|obstacle| {
+        let offset = position - obstacle.center();
+        let reach = obstacle.half_size() + radius;
+        obstacle.kind.blocks_spiders()
+            && velocity.x < 0.
+            && offset.y.abs() < reach.y
+            && offset.x > -reach.x
+            && offset.x < reach.x + DETOUR_LOOKAHEAD_PX
+    });
+    let Some(obstacle) = blocking else {
+        pathing.detour = Detour::None;
+        return velocity;
+    };
+    let limit = max_y(level);
+    let at_edge = |direction: f32| position.y * direction >= limit;
+    pathing.detour = match pathing.detour {
+        Detour::None => {
+            let direction = if position.y >= obstacle.center().y {
+                1.
+            } else {
+                -1.
+            };
+            if at_edge(direction) {
+                Detour::Back(-direction)
+            } else {
+                Detour::Around(direction)
+            }
+        }
+        Detour::Around(direction) if at_edge(direction) => Detour::Back(-direction),
+        Detour::Back(direction) if at_edge(direction) => Detour::Trample,
+        detour => detour,
+    };
+    match pathing.detour {
+        Detour::Around(direction) | Detour::Back(direction) => {
+            Vec2::new(0., direction * velocity.length())
+        }
+        Detour::None | Detour::Trample => velocity,
+    }
+}
+
+fn steer_spiders(
+    time: Res<Time>,
+    slime_query: Query<&Transform, With<Slime>>,
+    plant_query: Query<(Entity, &Transform, &Plant)>,
+    obstacle_query: Query<&Obstacle>,
+    mut spider_query: Query<(&Spider, &mut Pathing, &Transform, &mut Velocity)>,
+) {
+    let slimes: Vec<Vec2> = slime_query
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .collect();
+    let plants: Vec<(Entity, Vec2)> = plant_query
+        .iter()
+        .filter(|(_, _, plant)| !plant.is_eaten())
+        .map(|(entity, transform, _)| (entity, transform.translation.truncate()))
+        .collect();
+    let obstacles: Vec<Obstacle> = obstacle_query.iter().copied().collect();
+    let dt = time.delta_seconds();
+    for (spider, mut pathing, transform, mut velocity) in &mut spider_query {
+        let position = transform.translation.truncate();
+        let mut wanted = spider_velocity(spider, &mut pathing, position, &slimes, dt);
+        if position.x <= GARDEN_X + PLANT_SCENT_PX {
+            if let Some((_, plant)) = nearest_plant(position, plants.iter().copied()) {
+                wanted = (plant - position).normalize_or_zero() * spider.speed;
+            }
+        }
+        velocity.linvel = detour(spider.level, &mut pathing, position, wanted, &obstacles);
+    }
+}
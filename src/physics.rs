@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{AppState, PIXELS_PER_METER, PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH};
+
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(
+            PIXELS_PER_METER,
+        ))
+        .add_startup_system(setup_physics)
+        .add_system_set(
+            SystemSet::on_enter(AppState::GameOver).with_system(set_all_velocities_to_zero),
+        );
+    }
+}
+
+fn setup_physics(mut rapier_config: ResMut<RapierConfiguration>, mut commands: Commands) {
+    rapier_config.gravity = Vec2::ZERO;
+    let wall_size = 100.;
+    for (width_x, width_y, pos_x, pos_y) in [
+        (
+            wall_size,
+            2.0 * PLAYFIELD_HEIGHT,
+            -PLAYFIELD_WIDTH / 2. - wall_size / 2.,
+            0.,
+        ),
+        (
+            wall_size,
+            2.0 * PLAYFIELD_HEIGHT,
+            PLAYFIELD_WIDTH / 2. + wall_size / 2.,
+            0.,
+        ),
+        (
+            2.0 * PLAYFIELD_WIDTH,
+            wall_size,
+            0.,
+            -PLAYFIELD_HEIGHT / 2. - wall_size / 2.,
+        ),
+        (
+            2.0 * PLAYFIELD_WIDTH,
+            wall_size,
+            0.,
+            PLAYFIELD_HEIGHT / 2. + wall_size / 2.,
+        ),
+    ] {
+        commands
+            .spawn()
+            .insert(Collider::cuboid(width_x / 2., width_y / 2.))
+            .insert(CollisionGroups::default())
+            .insert_bundle(TransformBundle::from(Transform::from_xyz(pos_x, pos_y, 0.)));
+    }
+}
+
+fn set_all_velocities_to_zero(mut query: Query<&mut Velocity>) {
+    for mut velocity in &mut query {
+        *velocity = Velocity::zero();
+    }
+}
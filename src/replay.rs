@@ -0,0 +1,326 @@
+use std::{path::PathBuf, time::Duration};
+
+use bevy::{input::InputSystem, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    combine::OverflowPolicy,
+    defender::DefenderConfig,
+    headless::{AdvanceTime, HeadlessPlugin, HeadlessTimestep},
+    interaction::MousePosition,
+    rng::RngSeed,
+    score::ScoreResource,
+    splash::SplashConfig,
+    terrain::TerrainLayout,
+    waves::WaveConfig,
+    AppState, GameplaySystem,
+};
+
+/// Everything needed to play a run again: its seed, how long each frame took, and what the
+/// player did with the mouse.
+///
+/// Frames are counted from the one where the run starts.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    /// The length of each frame, in nanoseconds.
+    pub frame_nanos: Vec<u64>,
+    pub actions: Vec<ReplayAction>,
+    /// Whether slimes defended on their own, as set by [`DefenderConfig::enabled`].
+    #[serde(default)]
+    pub defenders: bool,
+    /// What merges over the max size turned into.
+    #[serde(default)]
+    pub overflow: OverflowPolicy,
+    /// Where the obstacles went.
+    #[serde(default)]
+    pub terrain: TerrainLayout,
+    /// How kills splashed, if they did.
+    #[serde(default)]
+    pub splash: SplashConfig,
+    /// The difficulty ladder spiders were spawned from, as it stood when the run started. Later
+    /// changes are in `wave_changes`.
+    #[serde(default)]
+    pub waves: WaveConfig,
+    /// Every time the ladder changed during the run, as it does when `waves.ron` is edited.
+    #[serde(default)]
+    pub wave_changes: Vec<WaveChange>,
+}
+
+/// The difficulty ladder that applied from `frame` on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaveChange {
+    pub frame: usize,
+    pub waves: WaveConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReplayAction {
+    pub frame: usize,
+    pub kind: ReplayActionKind,
+    /// Where the cursor was in the world, if it was over the window at all.
+    pub position: Option<(f32, f32)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayActionKind {
+    /// The left mouse button was pressed, which picks up whatever is under the cursor.
+    DragStart,
+    /// The left mouse button was released, which drops onto whatever is under the cursor.
+    Release,
+    /// The cursor moved while the left mouse button was held, dragging whatever was picked up
+    /// along with it.
+    Move,
+}
+
+impl Replay {
+    pub fn from_ron(ron: &str) -> ron::Result<Self> {
+        ron::from_str(ron)
+    }
+
+    pub fn to_ron(&self) -> ron::Result<String> {
+        ron::ser::to_string_pretty(self, default())
+    }
+}
+
+/// Records every run into a [`Replay`], and writes it to `path` when the run is over.
+#[derive(Default)]
+pub struct RecordPlugin {
+    pub path: Option<PathBuf>,
+}
+
+impl Plugin for RecordPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ReplayRecorder {
+            path: self.path.clone(),
+            recording: None,
+            finished: None,
+        })
+        .add_system_set(
+            SystemSet::on_enter(AppState::InGame)
+                .with_system(start_recording.after(GameplaySystem::StartRun)),
+        )
+        .add_system_to_stage(CoreStage::First, record_wave_changes)
+        .add_system_to_stage(CoreStage::Last, record_frame);
+    }
+}
+
+pub struct ReplayRecorder {
+    path: Option<PathBuf>,
+    recording: Option<Replay>,
+    /// The replay of the last run that ended.
+    pub finished: Option<Replay>,
+}
+
+fn start_recording(
+    seed: Res<RngSeed>,
+    defenders: Res<DefenderConfig>,
+    overflow: Res<OverflowPolicy>,
+    terrain: Res<TerrainLayout>,
+    splash: Res<SplashConfig>,
+    waves: Res<WaveConfig>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    recorder.recording = Some(Replay {
+        seed: seed.0,
+        defenders: defenders.enabled,
+        overflow: *overflow,
+        terrain: terrain.clone(),
+        splash: splash.clone(),
+        waves: waves.clone(),
+        ..default()
+    });
+}
+
+// runs at the start of the frame, ahead of every system the new ladder could make a difference
+// to.
+fn record_wave_changes(waves: Res<WaveConfig>, mut recorder: ResMut<ReplayRecorder>) {
+    let replay = match recorder.recording.as_mut() {
+        Some(replay) => replay,
+        None => return,
+    };
+    let current = replay
+        .wave_changes
+        .last()
+        .map_or(&replay.waves, |change| &change.waves);
+    if waves.is_changed() && *waves != *current {
+        replay.wave_changes.push(WaveChange {
+            frame: replay.frame_nanos.len(),
+            waves: waves.clone(),
+        });
+    }
+}
+
+// runs at the end of the frame so that it sees the input and time every in-game system saw,
+// including on the frames where the run starts and ends.
+fn record_frame(
+    time: Res<Time>,
+    state: Res<State<AppState>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mouse_position: Res<MousePosition>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    let recorder = &mut *recorder;
+    let replay = match recorder.recording.as_mut() {
+        Some(replay) => replay,
+        None => return,
+    };
+    let frame = replay.frame_nanos.len();
+    let position = mouse_position.0.map(|position| (position.x, position.y));
+    for (kind, happened) in [
+        (
+            ReplayActionKind::DragStart,
+            mouse_input.just_pressed(MouseButton::Left),
+        ),
+        (
+            ReplayActionKind::Release,
+            mouse_input.just_released(MouseButton::Left),
+        ),
+    ] {
+        if happened {
+            replay.actions.push(ReplayAction {
+                frame,
+                kind,
+                position,
+            });
+        }
+    }
+    // what a dragged slime passes over matters too, so follow the cursor until it's released.
+    if mouse_input.pressed(MouseButton::Left)
+        && replay.actions.last().map(|action| action.position) != Some(position)
+    {
+        replay.actions.push(ReplayAction {
+            frame,
+            kind: ReplayActionKind::Move,
+            position,
+        });
+    }
+    replay.frame_nanos.push(time.delta().as_nanos() as u64);
+    if *state.current() != AppState::InGame {
+        let replay = recorder.recording.take();
+        if let (Some(path), Some(replay)) = (&recorder.path, &replay) {
+            let written = replay
+                .to_ron()
+                .map_err(|err| err.to_string())
+                .and_then(|ron| std::fs::write(path, ron).map_err(|err| err.to_string()));
+            match written {
+                Ok(()) => info!("wrote replay to {}", path.display()),
+                Err(err) => warn!("could not write replay to {}: {err}", path.display()),
+            }
+        }
+        recorder.finished = replay;
+    }
+}
+
+/// Plays a [`Replay`] back on a headless app.
+///
+/// Add this before [`HeadlessPlugin`] so that the run uses the
+/// recorded seed, first frame length, defender setting, overflow policy, terrain, splash
+/// setting and waves. Each frame then takes as long as it did in the recording, the recorded
+/// mouse actions are fed into `Input<MouseButton>` and [`MousePosition`], and the waves change
+/// on the frames they did.
+pub struct ReplayPlugin {
+    pub replay: Replay,
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let player = ReplayPlayer {
+            replay: self.replay.clone(),
+            frame: 0,
+        };
+        if let Some(timestep) = player.timestep() {
+            app.insert_resource(HeadlessTimestep(timestep));
+        }
+        app.insert_resource(RngSeed(self.replay.seed))
+            .insert_resource(DefenderConfig {
+                enabled: self.replay.defenders,
+                ..default()
+            })
+            .insert_resource(self.replay.overflow)
+            .insert_resource(self.replay.terrain.clone())
+            .insert_resource(self.replay.splash.clone())
+            .insert_resource(self.replay.waves.clone())
+            .insert_resource(player)
+            .add_system_to_stage(CoreStage::PreUpdate, play_actions.after(InputSystem))
+            .add_system_to_stage(CoreStage::PreUpdate, play_wave_changes)
+            .add_system_to_stage(CoreStage::Last, next_replay_frame)
+            .add_system_to_stage(
+                CoreStage::Last,
+                set_replay_timestep
+                    .after(next_replay_frame)
+                    .before(AdvanceTime),
+            );
+    }
+}
+
+/// Plays `replay` to the end on a headless app, and returns how the run scored.
+pub fn play(replay: Replay) -> ScoreResource {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(ReplayPlugin { replay })
+        .add_plugin(HeadlessPlugin::default());
+    // the first update starts the run, even if the recording ended on the same frame.
+    loop {
+        app.update();
+        if app.world.resource::<ReplayPlayer>().finished() {
+            break;
+        }
+    }
+    app.world.resource::<ScoreResource>().clone()
+}
+
+pub struct ReplayPlayer {
+    replay: Replay,
+    frame: usize,
+}
+
+impl ReplayPlayer {
+    /// Whether every recorded frame has been played.
+    pub fn finished(&self) -> bool {
+        self.frame >= self.replay.frame_nanos.len()
+    }
+
+    fn timestep(&self) -> Option<Duration> {
+        self.replay
+            .frame_nanos
+            .get(self.frame)
+            .map(|&nanos| Duration::from_nanos(nanos))
+    }
+}
+
+fn play_actions(
+    player: Res<ReplayPlayer>,
+    mut mouse_input: ResMut<Input<MouseButton>>,
+    mut mouse_position: ResMut<MousePosition>,
+) {
+    for action in &player.replay.actions {
+        if action.frame != player.frame {
+            continue;
+        }
+        mouse_position.0 = action.position.map(|(x, y)| Vec2::new(x, y));
+        match action.kind {
+            ReplayActionKind::DragStart => mouse_input.press(MouseButton::Left),
+            ReplayActionKind::Release => mouse_input.release(MouseButton::Left),
+            ReplayActionKind::Move => {}
+        }
+    }
+}
+
+fn play_wave_changes(player: Res<ReplayPlayer>, mut waves: ResMut<WaveConfig>) {
+    for change in &player.replay.wave_changes {
+        if change.frame == player.frame {
+            *waves = change.waves.clone();
+        }
+    }
+}
+
+fn next_replay_frame(mut player: ResMut<ReplayPlayer>) {
+    player.frame += 1;
+}
+
+fn set_replay_timestep(player: Res<ReplayPlayer>, mut timestep: ResMut<HeadlessTimestep>) {
+    if let Some(next) = player.timestep() {
+        timestep.0 = next;
+    }
+}
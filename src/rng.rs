@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{AppState, GameplaySystem};
+
+/// Seeds all gameplay randomness through [`GameRng`].
+///
+/// Insert an [`RngSeed`] before adding this plugin to replay a particular run; otherwise a
+/// seed is picked at random. Every run after the first picks a new seed at random. Either
+/// way the seed of each run is logged.
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        let seed = match app.world.get_resource::<RngSeed>() {
+            Some(seed) => seed.0,
+            None => rand::thread_rng().gen(),
+        };
+        app.insert_resource(RngSeed(seed))
+            .insert_resource(GameRng::new(seed))
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(start_run.label(GameplaySystem::StartRun)),
+            );
+    }
+}
+
+/// The seed of the current run, or of the first run before the game starts.
+pub struct RngSeed(pub u64);
+
+/// The independent random streams the game draws from.
+///
+/// Each system gets its own stream so that, say, slimes wandering around more or less often
+/// doesn't change which spiders show up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    Background,
+    Slimes,
+    Movement,
+    Combine,
+    Spiders,
+    Loot,
+    Terrain,
+}
+
+impl RngStream {
+    // new streams go at the end so existing seeds keep producing the same runs.
+    const ALL: [Self; 7] = [
+        Self::Background,
+        Self::Slimes,
+        Self::Movement,
+        Self::Combine,
+        Self::Spiders,
+        Self::Loot,
+        Self::Terrain,
+    ];
+}
+
+pub struct GameRng {
+    seed: u64,
+    streams: Vec<StdRng>,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        let mut root = StdRng::seed_from_u64(seed);
+        let streams = RngStream::ALL
+            .iter()
+            .map(|_| StdRng::from_rng(&mut root).expect("seeding from a StdRng cannot fail"))
+            .collect();
+        Self { seed, streams }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+/// Restarts every stream from the run's seed, so that a run only depends on its own seed and
+/// not on what earlier runs or the menus drew.
+fn start_run(
+    mut seed: ResMut<RngSeed>,
+    mut game_rng: ResMut<GameRng>,
+    mut first_run_started: Local<bool>,
+) {
+    if *first_run_started {
+        seed.0 = rand::thread_rng().gen();
+    }
+    *first_run_started = true;
+    info!("rng seed: {}", seed.0);
+    *game_rng = GameRng::new(seed.0);
+}
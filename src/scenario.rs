@@ -0,0 +1,203 @@
+use bevy::{
+    hierarchy::despawn_with_children_recursive,
+    input::{mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+};
+
+use crate::{
+    boss::Boss,
+    headless::headless_app,
+    interaction::MousePosition,
+    score::ScoreResource,
+    slime::{Slime, SpawnSlimeEvent},
+    spider::{SpawnSpiderEvent, Spider, SpiderSpawnTimer},
+    terrain::TerrainLayout,
+    AppState,
+};
+
+/// Steps a headless game by hand: places slimes and spiders, moves and clicks the mouse, and
+/// looks at what came of it.
+///
+/// Mouse input goes through the same `MouseButtonInput` events a window would send, so
+/// everything downstream of them runs exactly as it does in the game.
+pub struct Scenario {
+    pub app: App,
+}
+
+impl Scenario {
+    /// Wraps an app that is already set up, such as one from [`headless_app`].
+    pub fn new(app: App) -> Self {
+        Self { app }
+    }
+
+    /// Starts a headless game on open ground, then clears out the starting slimes and stops
+    /// spiders from spawning on their own.
+    pub fn empty(seed: u64) -> Self {
+        Self::with_terrain(seed, TerrainLayout::Open)
+    }
+
+    /// Like [`Scenario::empty`], but with the obstacles `terrain` lays out.
+    pub fn with_terrain(seed: u64, terrain: TerrainLayout) -> Self {
+        let mut app = headless_app(seed);
+        app.insert_resource(terrain);
+        let mut scenario = Self::new(app);
+        scenario.step();
+        let entities: Vec<Entity> = scenario
+            .app
+            .world
+            .query_filtered::<Entity, Or<(With<Slime>, With<Spider>)>>()
+            .iter(&scenario.app.world)
+            .collect();
+        for entity in entities {
+            despawn_with_children_recursive(&mut scenario.app.world, entity);
+        }
+        scenario
+            .app
+            .world
+            .resource_mut::<SpiderSpawnTimer>()
+            .0
+            .pause();
+        scenario
+    }
+
+    pub fn step(&mut self) -> &mut Self {
+        self.app.update();
+        self
+    }
+
+    pub fn step_n(&mut self, updates: usize) -> &mut Self {
+        for _ in 0..updates {
+            self.app.update();
+        }
+        self
+    }
+
+    /// Spawns `slime` at `position` and steps once so that it exists.
+    pub fn spawn_slime(&mut self, slime: Slime, position: Vec2) -> Entity {
+        self.spawn_with(|world| world.send_event(SpawnSlimeEvent { slime, position }))
+    }
+
+    /// Spawns `spider` at `position` and steps once so that it exists.
+    pub fn spawn_spider(&mut self, spider: Spider, position: Vec2) -> Entity {
+        self.spawn_with(|world| {
+            world.send_event(SpawnSpiderEvent {
+                spider,
+                position,
+                boss: None,
+            })
+        })
+    }
+
+    /// Spawns `boss`, carried by `spider`, at `position` and steps once so that it exists.
+    pub fn spawn_boss(&mut self, boss: Boss, spider: Spider, position: Vec2) -> Entity {
+        self.spawn_with(|world| {
+            world.send_event(SpawnSpiderEvent {
+                spider,
+                position,
+                boss: Some(boss),
+            })
+        })
+    }
+
+    fn spawn_with(&mut self, send: impl FnOnce(&mut World)) -> Entity {
+        let before = self.entities();
+        send(&mut self.app.world);
+        self.step();
+        self.entities()
+            .into_iter()
+            .find(|entity| !before.contains(entity))
+            .expect("nothing was spawned")
+    }
+
+    fn entities(&mut self) -> Vec<Entity> {
+        self.app
+            .world
+            .query_filtered::<Entity, Or<(With<Slime>, With<Spider>)>>()
+            .iter(&self.app.world)
+            .collect()
+    }
+
+    /// Puts the cursor at `position` for the next update, or takes it off the window.
+    pub fn move_mouse(&mut self, position: Option<Vec2>) -> &mut Self {
+        self.app.world.resource_mut::<MousePosition>().0 = position;
+        self
+    }
+
+    /// Presses the left mouse button at `position` and steps once.
+    pub fn press(&mut self, position: Vec2) -> &mut Self {
+        self.move_mouse(Some(position));
+        self.mouse_button(ButtonState::Pressed);
+        self.step()
+    }
+
+    /// Releases the left mouse button at `position` and steps once.
+    pub fn release(&mut self, position: Vec2) -> &mut Self {
+        self.move_mouse(Some(position));
+        self.mouse_button(ButtonState::Released);
+        self.step()
+    }
+
+    fn mouse_button(&mut self, state: ButtonState) {
+        self.app.world.send_event(MouseButtonInput {
+            button: MouseButton::Left,
+            state,
+        });
+    }
+
+    /// Drags whatever is under the cursor at `from` and drops it at `to`, a few updates later.
+    pub fn drag(&mut self, from: Vec2, to: Vec2) -> &mut Self {
+        self.press(from);
+        for i in 1..=3 {
+            self.move_mouse(Some(from.lerp(to, i as f32 / 4.)));
+            self.step();
+        }
+        self.release(to)
+    }
+
+    /// Drags `entity` onto wherever `target` is now.
+    pub fn drag_onto(&mut self, entity: Entity, target: Entity) -> &mut Self {
+        let from = self.position(entity);
+        self.press(from);
+        let to = self.position(target);
+        self.release(to)
+    }
+
+    pub fn position(&self, entity: Entity) -> Vec2 {
+        self.app
+            .world
+            .get::<Transform>(entity)
+            .expect("entity has no transform")
+            .translation
+            .truncate()
+    }
+
+    pub fn exists(&self, entity: Entity) -> bool {
+        self.app.world.get_entity(entity).is_some()
+    }
+
+    pub fn slimes(&mut self) -> Vec<Slime> {
+        self.app
+            .world
+            .query::<&Slime>()
+            .iter(&self.app.world)
+            .copied()
+            .collect()
+    }
+
+    pub fn spiders(&mut self) -> Vec<Spider> {
+        self.app
+            .world
+            .query::<&Spider>()
+            .iter(&self.app.world)
+            .copied()
+            .collect()
+    }
+
+    pub fn score(&self) -> &ScoreResource {
+        self.app.world.resource::<ScoreResource>()
+    }
+
+    pub fn state(&self) -> &AppState {
+        self.app.world.resource::<State<AppState>>().current()
+    }
+}
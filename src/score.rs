@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+use crate::{spider::Spider, AppState, GameplaySystem, GARDEN_X};
+
+/// Points a kill is worth per level of the spider, before the streak multiplier.
+pub const POINTS_PER_LEVEL: u32 = 10;
+/// How many attacks in a row that hurt their spider it takes to raise the multiplier by one.
+pub const STREAK_PER_MULTIPLIER: u32 = 3;
+pub const MAX_MULTIPLIER: u32 = 5;
+/// How close to the garden a kill has to be to count as a close call, in pixels.
+pub const CLOSE_CALL_PX: f32 = 160.;
+/// Points on top of a close call's kill points.
+pub const CLOSE_CALL_BONUS: u32 = 25;
+
+/// Keeps score over a run, and counts how long it has lasted.
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(AppState::InGame).with_system(reset_score))
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(count_survival_time.after(GameplaySystem::Combine)),
+            );
+    }
+}
+
+/// Everything the run is scored on.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScoreResource {
+    pub spiders_killed: u32,
+    pub spiders_spawned: u32,
+    pub points: u32,
+    /// Attacks in a row that hurt their spider, which any attack that does no damage breaks.
+    pub streak: u32,
+    pub best_streak: u32,
+    /// Kills within [`CLOSE_CALL_PX`] of the garden.
+    pub close_calls: u32,
+    pub seconds_survived: f32,
+}
+
+impl ScoreResource {
+    /// What kill points are multiplied by at the current streak.
+    pub fn multiplier(&self) -> u32 {
+        (1 + self.streak / STREAK_PER_MULTIPLIER).min(MAX_MULTIPLIER)
+    }
+
+    /// Counts an attack that did `damage` towards the streak, or ends the streak if it did none.
+    pub fn attack(&mut self, damage: u32) {
+        if damage == 0 {
+            self.streak = 0;
+            return;
+        }
+        self.streak += 1;
+        self.best_streak = self.best_streak.max(self.streak);
+    }
+
+    /// Scores killing `spider` at `position`, and returns the points it earned.
+    ///
+    /// That's [`POINTS_PER_LEVEL`] for each of the spider's levels times the
+    /// [`ScoreResource::multiplier`], plus [`CLOSE_CALL_BONUS`] if it was a close call.
+    pub fn kill(&mut self, spider: &Spider, position: Vec2) -> u32 {
+        let mut points = spider.level * POINTS_PER_LEVEL * self.multiplier();
+        if position.x - GARDEN_X <= CLOSE_CALL_PX {
+            self.close_calls += 1;
+            points += CLOSE_CALL_BONUS;
+        }
+        self.points += points;
+        points
+    }
+}
+
+fn reset_score(mut commands: Commands) {
+    commands.insert_resource(ScoreResource::default());
+}
+
+fn count_survival_time(time: Res<Time>, mut score: ResMut<ScoreResource>) {
+    score.seconds_survived += time.delta_seconds();
+}
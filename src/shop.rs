@@ -0,0 +1,225 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+use crate::{
+    assets::FontResources,
+    camera::PlayfieldAnchor,
+    combine::SpiderKilledEvent,
+    interaction::{circle_shape, MousePosition},
+    slime::{Slime, SlimeColor, SpawnSlimeEvent},
+    spider::Spider,
+    AppState, GameplaySystem, GARDEN_X, LABEL_LAYER, PLAYFIELD_WIDTH,
+};
+
+/// How much goo a run starts with.
+pub const STARTING_GOO: u32 = 0;
+/// How much goo a spider is worth per level when it's killed.
+pub const GOO_PER_LEVEL: u32 = 1;
+/// What a size-1 slime costs at the shop.
+pub const SLIME_PRICE: u32 = 3;
+/// How big a shop slot is, and how far one is from the next, in pixels.
+pub const SHOP_SLOT_RADIUS_PX: f32 = 24.;
+pub const SHOP_SLOT_SPACING_PX: f32 = 72.;
+
+/// Goo earned from killing spiders, and the shop down the side of the garden that spends it on
+/// new slimes.
+///
+/// The shop sits in the playfield rather than the UI, so that buying is a click in the world
+/// like any other and plays back from a [`crate::replay::Replay`].
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Goo(STARTING_GOO))
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(reset_goo)
+                    .with_system(spawn_shop)
+                    .with_system(setup_goo_text),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    // a click on the shop is a purchase, not the start of a drag.
+                    .with_system(
+                        buy_slimes
+                            .after(GameplaySystem::Movement)
+                            .before(GameplaySystem::Drag),
+                    )
+                    .with_system(earn_goo.after(GameplaySystem::Combine))
+                    .with_system(show_goo.after(buy_slimes).after(earn_goo)),
+            )
+            .add_system_set(SystemSet::on_enter(AppState::GameOver).with_system(despawn_shop));
+    }
+}
+
+/// The goo the player has to spend this run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Goo(pub u32);
+
+/// How much goo killing `spider` earns.
+pub fn goo_reward(spider: &Spider) -> u32 {
+    spider.level * GOO_PER_LEVEL
+}
+
+/// A place in the shop that sells slimes of `color`.
+#[derive(Component)]
+pub struct ShopSlot {
+    pub color: SlimeColor,
+}
+
+impl ShopSlot {
+    /// Where the slot for `color` sits, top to bottom in the order of [`SlimeColor::ALL`].
+    pub fn position(color: SlimeColor) -> Vec2 {
+        let index = SlimeColor::ALL
+            .iter()
+            .position(|&other| other == color)
+            .unwrap_or_default();
+        let top = (SlimeColor::ALL.len() - 1) as f32 * SHOP_SLOT_SPACING_PX / 2.;
+        Vec2::new(
+            -PLAYFIELD_WIDTH / 2. + 40.,
+            top - index as f32 * SHOP_SLOT_SPACING_PX,
+        )
+    }
+}
+
+/// The "GOO n" readout under the garden's.
+#[derive(Component)]
+pub struct GooText;
+
+fn reset_goo(mut goo: ResMut<Goo>) {
+    *goo = Goo(STARTING_GOO);
+}
+
+fn spawn_shop(mut commands: Commands, fonts: Res<FontResources>) {
+    for color in SlimeColor::ALL {
+        let position = ShopSlot::position(color);
+        commands
+            .spawn_bundle(circle_shape(SHOP_SLOT_RADIUS_PX, Color::WHITE))
+            .insert(Transform::from_translation(position.extend(LABEL_LAYER)))
+            .insert(ShopSlot { color })
+            .with_children(|parent| {
+                parent.spawn_bundle(Text2dBundle {
+                    text: Text::from_section(
+                        format!("{SLIME_PRICE}"),
+                        TextStyle {
+                            font: fonts.game.clone(),
+                            font_size: 20.,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_alignment(TextAlignment::CENTER),
+                    transform: Transform::from_xyz(0., 0., 0.1),
+                    ..default()
+                });
+            });
+    }
+}
+
+/// Spends goo on a slime when a slot that the player can afford is clicked, and drops the slime
+/// just outside the garden.
+fn buy_slimes(
+    mouse_input: Res<Input<MouseButton>>,
+    mouse_position: Res<MousePosition>,
+    mut goo: ResMut<Goo>,
+    slot_query: Query<(&ShopSlot, &Transform)>,
+    mut events: EventWriter<SpawnSlimeEvent>,
+) {
+    if !mouse_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(mouse_pos) = mouse_position.0 else {
+        return;
+    };
+    for (slot, transform) in &slot_query {
+        let position = transform.translation.truncate();
+        if position.distance(mouse_pos) >= SHOP_SLOT_RADIUS_PX || goo.0 < SLIME_PRICE {
+            continue;
+        }
+        goo.0 -= SLIME_PRICE;
+        events.send(SpawnSlimeEvent {
+            slime: Slime {
+                color: slot.color,
+                size: 1,
+            },
+            position: Vec2::new(GARDEN_X + 40., position.y),
+        });
+        break;
+    }
+}
+
+fn earn_goo(mut goo: ResMut<Goo>, mut events: EventReader<SpiderKilledEvent>) {
+    for ev in events.iter() {
+        goo.0 += goo_reward(&ev.spider);
+    }
+}
+
+fn setup_goo_text(mut commands: Commands, fonts: Res<FontResources>) {
+    commands
+        .spawn_bundle(
+            TextBundle::from_sections([
+                TextSection {
+                    value: "GOO ".to_owned(),
+                    style: TextStyle {
+                        font: fonts.game.clone(),
+                        font_size: 24.,
+                        color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+                    },
+                },
+                TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        font: fonts.game.clone(),
+                        font_size: 32.,
+                        color: Color::WHITE,
+                    },
+                },
+            ])
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Px(16.0),
+                    top: Val::Px(40.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(PlayfieldAnchor::at(16.0, 40.0))
+        .insert(GooText);
+}
+
+/// Keeps the readout up to date, and fills in the slots the player can afford.
+fn show_goo(
+    goo: Res<Goo>,
+    mut text_query: Query<&mut Text, With<GooText>>,
+    mut slot_query: Query<(&ShopSlot, &mut DrawMode)>,
+) {
+    for mut text in &mut text_query {
+        text.sections[1].value = format!("{}", goo.0);
+    }
+    let affordable = goo.0 >= SLIME_PRICE;
+    for (slot, mut draw_mode) in &mut slot_query {
+        if let DrawMode::Outlined {
+            ref mut fill_mode,
+            ref mut outline_mode,
+        } = *draw_mode
+        {
+            fill_mode.color = if affordable {
+                slot.color.color()
+            } else {
+                *slot.color.color().set_a(0.25)
+            };
+            outline_mode.color = if affordable {
+                Color::WHITE
+            } else {
+                Color::rgba(1., 1., 1., 0.25)
+            };
+        }
+    }
+}
+
+fn despawn_shop(mut commands: Commands, query: Query<Entity, Or<(With<ShopSlot>, With<GooText>)>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+
+use crate::{
+    combine::spider_damage,
+    garden::Garden,
+    headless::headless_app,
+    scenario::Scenario,
+    slime::{Slime, SLIME_SIZE_MAX},
+    spider::{Spider, SpiderHealth},
+    waves::WaveConfig,
+    AppState,
+};
+
+/// What a bot can see of the field when it's asked to act.
+pub struct Field {
+    pub slimes: Vec<(Entity, Slime, Vec2)>,
+    pub spiders: Vec<(Entity, Spider, SpiderHealth, Vec2)>,
+}
+
+/// A mouse move a bot can make.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotAction {
+    /// Drag the first entity onto the second, to merge or to attack.
+    DragOnto(Entity, Entity),
+}
+
+/// A player policy for [`simulate`].
+pub trait Bot {
+    fn name(&self) -> &str;
+
+    /// Decides what to do, if anything. Called every [`SimulationConfig::reaction_updates`].
+    fn act(&mut self, field: &Field) -> Option<BotAction>;
+}
+
+/// Never touches the mouse, which shows how long spiders alone take to reach the garden.
+pub struct IdleBot;
+
+impl Bot for IdleBot {
+    fn name(&self) -> &str {
+        "idle"
+    }
+
+    fn act(&mut self, _field: &Field) -> Option<BotAction> {
+        None
+    }
+}
+
+/// Goes after the spider closest to the garden: finishes it off with the smallest slime that
+/// can, or else hits it with whichever slime hurts it most. Only when no slime can hurt it,
+/// as with an armored spider, does it grow a slime of its weakness color by merging the two
+/// smallest of them.
+pub struct GreedyBot;
+
+impl Bot for GreedyBot {
+    fn name(&self) -> &str {
+        "greedy"
+    }
+
+    fn act(&mut self, field: &Field) -> Option<BotAction> {
+        let mut spiders: Vec<_> = field.spiders.iter().collect();
+        spiders.sort_by(|a, b| a.3.x.total_cmp(&b.3.x));
+        for &&(spider_entity, spider, health, _) in &spiders {
+            let mut slimes: Vec<_> = field.slimes.iter().collect();
+            slimes.sort_by_key(|(_, slime, _)| slime.size);
+            let killer = slimes
+                .iter()
+                .find(|(_, slime, _)| spider_damage(&spider, slime) >= health.hp);
+            // a size-1 slime comes back whole from an attack, so chipping away costs nothing.
+            let hardest_hitter = slimes
+                .iter()
+                .filter(|(_, slime, _)| spider_damage(&spider, slime) > 0)
+                .min_by_key(|(_, slime, _)| std::cmp::Reverse(spider_damage(&spider, slime)));
+            if let Some(&&(slime_entity, ..)) = killer.or(hardest_hitter) {
+                return Some(BotAction::DragOnto(slime_entity, spider_entity));
+            }
+            let candidates: Vec<_> = slimes
+                .into_iter()
+                .filter(|(_, slime, _)| slime.color == spider.weakness)
+                .collect();
+            if let [(addition, small, _), (base, next, _), ..] = candidates[..] {
+                if small.size + next.size <= SLIME_SIZE_MAX {
+                    return Some(BotAction::DragOnto(*addition, *base));
+                }
+            }
+        }
+        None
+    }
+}
+
+pub struct SimulationConfig {
+    /// Give up on a run that lasts longer than this.
+    pub max_seconds: f64,
+    /// How many updates pass between the bot's moves, as a stand-in for reaction time.
+    pub reaction_updates: usize,
+    /// The difficulty ladder to play against.
+    pub waves: WaveConfig,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            max_seconds: 60. * 30.,
+            reaction_updates: 30,
+            waves: WaveConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LossCause {
+    /// The garden ran out of health, and `spider` was the one that finished it off.
+    GardenReached { spider: Spider },
+    /// The run hit [`SimulationConfig::max_seconds`].
+    Timeout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunReport {
+    pub seed: u64,
+    pub cause: LossCause,
+    pub survival_seconds: f64,
+    pub spiders_killed: u32,
+    pub spiders_spawned: u32,
+}
+
+/// Plays one headless game with `bot` until it's lost or times out.
+pub fn simulate(seed: u64, bot: &mut dyn Bot, config: &SimulationConfig) -> RunReport {
+    let mut app = headless_app(seed);
+    app.insert_resource(config.waves.clone());
+    let mut scenario = Scenario::new(app);
+    let mut updates = 0;
+    while *scenario.state() == AppState::InGame && seconds(&scenario) < config.max_seconds {
+        if updates % config.reaction_updates.max(1) == 0 {
+            let field = field(&mut scenario);
+            match bot.act(&field) {
+                Some(BotAction::DragOnto(entity, target)) => {
+                    let from = scenario.position(entity);
+                    scenario.press(from);
+                    // the target can die to something else while the slime is picked up, and
+                    // then the slime just goes back down.
+                    let to = if scenario.exists(target) {
+                        scenario.position(target)
+                    } else {
+                        from
+                    };
+                    scenario.release(to);
+                    updates += 2;
+                    continue;
+                }
+                None => {}
+            }
+        }
+        scenario.step();
+        updates += 1;
+    }
+    let cause = if *scenario.state() == AppState::InGame {
+        LossCause::Timeout
+    } else {
+        let spider = scenario
+            .app
+            .world
+            .resource::<Garden>()
+            .last_breach
+            .expect("the game ended without a spider in the garden");
+        LossCause::GardenReached { spider }
+    };
+    let score = scenario.score();
+    RunReport {
+        seed,
+        cause,
+        survival_seconds: seconds(&scenario),
+        spiders_killed: score.spiders_killed,
+        spiders_spawned: score.spiders_spawned,
+    }
+}
+
+fn seconds(scenario: &Scenario) -> f64 {
+    scenario
+        .app
+        .world
+        .resource::<Time>()
+        .seconds_since_startup()
+}
+
+fn field(scenario: &mut Scenario) -> Field {
+    let world = &mut scenario.app.world;
+    Field {
+        slimes: world
+            .query::<(Entity, &Slime, &Transform)>()
+            .iter(world)
+            .map(|(entity, &slime, transform)| (entity, slime, transform.translation.truncate()))
+            .collect(),
+        spiders: world
+            .query::<(Entity, &Spider, &SpiderHealth, &Transform)>()
+            .iter(world)
+            .map(|(entity, &spider, &health, transform)| {
+                (entity, spider, health, transform.translation.truncate())
+            })
+            .collect(),
+    }
+}
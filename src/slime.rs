@@ -0,0 +1,313 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    animation::{AnimationTimer, SpriteAnimation},
+    assets::{FontResources, SlimeResources},
+    defender::{Defender, DefenderConfig, DefenderTimers},
+    interaction::{DragActive, HoverActive, Interactable},
+    rng::{GameRng, RngStream},
+    spider::Webbed,
+    AppState, GameplaySystem, LABEL_LAYER, MAIN_LAYER, PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH,
+};
+
+pub const SLIME_RADIUS_PX: f32 = 14.;
+pub const SLIME_SIZE_MIN: u32 = 1;
+pub const SLIME_SIZE_MAX: u32 = 5;
+/// How quickly slimes come to a stop once pushed, on open ground.
+pub const SLIME_LINEAR_DAMPING: f32 = 2.;
+
+pub struct SlimePlugin;
+
+impl Plugin for SlimePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SpawnSlimeEvent>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(spawn_initial_slimes.after(GameplaySystem::StartRun)),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(slime_drag_animation)
+                    .with_system(slime_spawner.after(GameplaySystem::Combine))
+                    .with_system(resize_slimes.after(GameplaySystem::Combine))
+                    .with_system(random_movement.label(GameplaySystem::Movement)),
+            )
+            .add_system_set(SystemSet::on_enter(AppState::GameOver).with_system(despawn_slime_text))
+            .add_system_set(SystemSet::on_exit(AppState::GameOver).with_system(despawn_slimes));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SlimeColor {
+    Red,
+    Green,
+    Blue,
+    Cyan,
+    Purple,
+    Yellow,
+    White,
+    Black,
+}
+
+impl SlimeColor {
+    pub const ALL: [Self; 8] = [
+        Self::Red,
+        Self::Green,
+        Self::Blue,
+        Self::Cyan,
+        Self::Purple,
+        Self::Yellow,
+        Self::Black,
+        Self::White,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SlimeColor::Red => "red",
+            SlimeColor::Green => "green",
+            SlimeColor::Blue => "blue",
+            SlimeColor::Cyan => "cyan",
+            SlimeColor::Purple => "purple",
+            SlimeColor::Yellow => "yellow",
+            SlimeColor::White => "white",
+            SlimeColor::Black => "black",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            SlimeColor::Red => Color::rgb_u8(224, 84, 66),
+            SlimeColor::Green => Color::rgb_u8(79, 175, 73),
+            SlimeColor::Blue => Color::rgb_u8(69, 140, 192),
+            SlimeColor::Cyan => Color::rgb_u8(0, 200, 221),
+            SlimeColor::Purple => Color::rgb_u8(159, 84, 205),
+            SlimeColor::Yellow => Color::rgb_u8(232, 208, 85),
+            SlimeColor::White => Color::WHITE,
+            SlimeColor::Black => Color::rgb_u8(11, 11, 11),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Slime {
+    pub color: SlimeColor,
+    pub size: u32,
+}
+
+impl Slime {
+    /// How far the slime's body reaches from its middle, which grows with its size.
+    pub fn radius_px(&self) -> f32 {
+        (1. + self.size as f32) * SLIME_RADIUS_PX
+    }
+}
+
+#[derive(Component)]
+pub struct RandomMovement {
+    pub chance_to_move: f32,
+    pub speed: f32,
+}
+
+#[derive(Component)]
+pub struct SlimeAnimation;
+
+/// The "LVL n" label, a child of its slime.
+#[derive(Component)]
+pub struct SlimeText;
+
+pub struct SpawnSlimeEvent {
+    pub slime: Slime,
+    pub position: Vec2,
+}
+
+fn slime_drag_animation(
+    slime_query: Query<(&Slime, &DragActive, &Children), Changed<DragActive>>,
+    mut sprite_query: Query<(&mut SpriteAnimation, &mut TextureAtlasSprite)>,
+) {
+    for (_slime, drag_active, children) in &slime_query {
+        for &child in children.iter() {
+            if let Ok((mut animation, mut sprite)) = sprite_query.get_mut(child) {
+                if drag_active.0 {
+                    sprite.color = Color::rgba(1., 1., 1., 0.5);
+                    *animation = SpriteAnimation::slime_drag();
+                } else {
+                    sprite.color = Color::WHITE;
+                    *animation = SpriteAnimation::slime_idle();
+                }
+            }
+        }
+    }
+}
+
+fn random_movement(
+    mut game_rng: ResMut<GameRng>,
+    mut query: Query<(&RandomMovement, &mut Velocity), Without<Webbed>>,
+) {
+    let rng = game_rng.stream(RngStream::Movement);
+    for (random_movement, mut velocity) in &mut query {
+        if rng.gen::<f32>() < random_movement.chance_to_move {
+            let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+            *velocity =
+                Velocity::linear(velocity.linvel + Vec2::from_angle(angle) * random_movement.speed);
+        }
+    }
+}
+
+fn slime_spawner(
+    mut commands: Commands,
+    fonts: Res<FontResources>,
+    slime_resources: Res<SlimeResources>,
+    defenders: Res<DefenderConfig>,
+    mut events: EventReader<SpawnSlimeEvent>,
+) {
+    for ev in events.iter() {
+        let radius_px = ev.slime.radius_px();
+        commands
+            .spawn_bundle(SpatialBundle {
+                transform: Transform::from_translation(ev.position.extend(0.)),
+                ..default()
+            })
+            .insert(ev.slime)
+            .insert(Interactable {
+                activation_radius: radius_px,
+            })
+            .insert(DragActive(false))
+            .insert(HoverActive(false))
+            .insert(RandomMovement {
+                chance_to_move: 5e-3,
+                speed: 200.,
+            })
+            .insert(Defender::default())
+            .insert(DefenderTimers::new(&defenders))
+            // rapier components
+            .insert(RigidBody::Dynamic)
+            .insert(Collider::ball(radius_px))
+            .insert(LockedAxes::ROTATION_LOCKED)
+            .insert(CollisionGroups::default())
+            .insert(Restitution::coefficient(0.5))
+            .insert(Velocity::zero())
+            .insert(Damping {
+                linear_damping: SLIME_LINEAR_DAMPING,
+                ..default()
+            })
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(SpriteSheetBundle {
+                        texture_atlas: slime_resources
+                            .texture_atlases
+                            .get(&ev.slime.color)
+                            .expect("texture atlas not found")
+                            .clone(),
+                        transform: sprite_transform(&ev.slime),
+                        ..default()
+                    })
+                    .insert(AnimationTimer(Timer::from_seconds(0.2, true)))
+                    .insert(SpriteAnimation::slime_idle());
+                let font = fonts.game.clone();
+                let lvl_text = TextSection {
+                    value: "LVL ".to_owned(),
+                    style: TextStyle {
+                        font: font.clone(),
+                        color: Color::rgba(1., 1., 1., 0.5),
+                        ..default()
+                    },
+                };
+                let number_text = TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        font,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                };
+                let mut text = Text::from_sections([lvl_text, number_text])
+                    .with_alignment(TextAlignment::CENTER);
+                label_text(&ev.slime, &mut text);
+                parent
+                    .spawn_bundle(Text2dBundle {
+                        text,
+                        transform: Transform::from_xyz(0., radius_px, LABEL_LAYER),
+                        ..default()
+                    })
+                    .insert(SlimeText);
+            });
+    }
+}
+
+/// Where a slime's sprite sits and how big it's drawn, relative to the slime.
+fn sprite_transform(slime: &Slime) -> Transform {
+    let scale = 1. + slime.size as f32;
+    Transform::from_xyz(-14.5 * scale, 1. * scale, MAIN_LAYER).with_scale(Vec3::splat(scale))
+}
+
+/// Sets the size shown on a slime's "LVL n" label, which also grows with it.
+fn label_text(slime: &Slime, text: &mut Text) {
+    text.sections[0].style.font_size = 12. + slime.size as f32 * 4.;
+    text.sections[1].value = format!("{}", slime.size);
+    text.sections[1].style.font_size = 24. + slime.size as f32 * 8.;
+}
+
+/// Fits the body, sprite and label of slimes whose size changed in place to their new size.
+fn resize_slimes(
+    mut slime_query: Query<
+        (
+            &Slime,
+            &mut Collider,
+            &mut Interactable,
+            &Children,
+            ChangeTrackers<Slime>,
+        ),
+        Changed<Slime>,
+    >,
+    mut sprite_query: Query<&mut Transform, (With<TextureAtlasSprite>, Without<SlimeText>)>,
+    mut text_query: Query<(&mut Text, &mut Transform), With<SlimeText>>,
+) {
+    for (slime, mut collider, mut interactable, children, tracker) in &mut slime_query {
+        // new slimes are spawned the right size, and swapping their collider would only make
+        // rapier rebuild it.
+        if tracker.is_added() {
+            continue;
+        }
+        let radius_px = slime.radius_px();
+        *collider = Collider::ball(radius_px);
+        interactable.activation_radius = radius_px;
+        for &child in children.iter() {
+            if let Ok(mut transform) = sprite_query.get_mut(child) {
+                *transform = sprite_transform(slime);
+            }
+            if let Ok((mut text, mut transform)) = text_query.get_mut(child) {
+                label_text(slime, &mut text);
+                transform.translation.y = radius_px;
+            }
+        }
+    }
+}
+
+fn spawn_initial_slimes(mut game_rng: ResMut<GameRng>, mut events: EventWriter<SpawnSlimeEvent>) {
+    let rng = game_rng.stream(RngStream::Slimes);
+    for _ in 0..2 {
+        for &color in SlimeColor::ALL.iter() {
+            // keep a little clear of the walls.
+            let x = rng.gen_range(0.0..PLAYFIELD_WIDTH) - PLAYFIELD_WIDTH / 2.;
+            let y = rng.gen_range(0.0..PLAYFIELD_HEIGHT) - PLAYFIELD_HEIGHT / 2.;
+            events.send(SpawnSlimeEvent {
+                slime: Slime { color, size: 1 },
+                position: 0.9 * Vec2::new(x, y),
+            });
+        }
+    }
+}
+
+fn despawn_slime_text(mut commands: Commands, query: Query<Entity, With<SlimeText>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn despawn_slimes(mut commands: Commands, query: Query<Entity, With<Slime>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
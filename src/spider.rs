@@ -0,0 +1,444 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    animation::{AnimationTimer, SpriteAnimation},
+    assets::{FontResources, SpiderResources},
+    boss::{Boss, BOSS_TINT},
+    interaction::{DragActive, HoverActive, Interactable},
+    pathing::{Pathing, SpiderMovement},
+    rng::{GameRng, RngStream},
+    score::ScoreResource,
+    slime::{Slime, SlimeColor},
+    waves::WaveConfig,
+    AppState, GameplaySystem, LABEL_LAYER, MAIN_LAYER, PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH,
+};
+
+pub const SPIDER_RADIUS_PX: f32 = 18.;
+
+/// How much faster than the rest scouts walk.
+pub const SCOUT_SPEED_MULTIPLIER: f32 = 1.75;
+/// Armored spiders take no damage from slimes smaller than this.
+pub const ARMORED_MIN_SLIME_SIZE: u32 = 3;
+/// How many spiders a brood mother splits into when it dies.
+pub const BROOD_SIZE: usize = 2;
+/// How close a slime has to be to a web spitter to get webbed.
+pub const WEB_RADIUS_PX: f32 = 150.;
+/// Seconds between a web spitter's webs.
+pub const WEB_INTERVAL_SECS: f32 = 4.;
+/// Seconds a web holds a slime in place.
+pub const WEB_DURATION_SECS: f32 = 2.;
+
+pub struct SpiderPlugin;
+
+impl Plugin for SpiderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SpawnSpiderEvent>()
+            .init_resource::<WaveConfig>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame).with_system(setup_spider_spawn_timer),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(
+                        spider_spawner
+                            .after(spider_spawn_timer)
+                            .after(GameplaySystem::Combine),
+                    )
+                    .with_system(spider_spawn_timer.before(GameplaySystem::Combine))
+                    .with_system(show_spider_health.after(GameplaySystem::Combine))
+                    .with_system(
+                        spit_webs
+                            .after(GameplaySystem::Combine)
+                            .before(GameplaySystem::Abilities),
+                    )
+                    .with_system(wear_off_webs.after(spit_webs)),
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::GameOver).with_system(despawn_spider_text),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::GameOver).with_system(despawn_spiders));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct Spider {
+    pub level: u32,
+    pub weakness: SlimeColor,
+    pub speed: f32,
+    pub kind: SpiderKind,
+    pub movement: SpiderMovement,
+}
+
+/// What sort of spider it is, which decides how it gets in the way besides walking to the
+/// garden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum SpiderKind {
+    #[default]
+    Common,
+    /// Walks [`SCOUT_SPEED_MULTIPLIER`] times as fast.
+    Scout,
+    /// Shrugs off slimes smaller than [`ARMORED_MIN_SLIME_SIZE`].
+    Armored,
+    /// Splits into [`BROOD_SIZE`] common spiders a level lower when it dies.
+    BroodMother,
+    /// Webs up the slimes around it every so often, so they can't move or be picked up for a
+    /// while.
+    WebSpitter,
+}
+
+impl SpiderKind {
+    pub const ALL: [Self; 5] = [
+        Self::Common,
+        Self::Scout,
+        Self::Armored,
+        Self::BroodMother,
+        Self::WebSpitter,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpiderKind::Common => "common",
+            SpiderKind::Scout => "scout",
+            SpiderKind::Armored => "armored",
+            SpiderKind::BroodMother => "brood mother",
+            SpiderKind::WebSpitter => "web spitter",
+        }
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        match self {
+            SpiderKind::Scout => SCOUT_SPEED_MULTIPLIER,
+            _ => 1.,
+        }
+    }
+
+    /// Tints the gray spider sprite so the kinds can be told apart.
+    pub fn tint(&self) -> Color {
+        match self {
+            SpiderKind::Common => Color::WHITE,
+            SpiderKind::Scout => Color::rgb_u8(255, 230, 150),
+            SpiderKind::Armored => Color::rgb_u8(150, 170, 210),
+            SpiderKind::BroodMother => Color::rgb_u8(230, 150, 210),
+            SpiderKind::WebSpitter => Color::rgb_u8(180, 240, 180),
+        }
+    }
+}
+
+/// How many hit points a spider has per level.
+pub const SPIDER_HP_PER_LEVEL: u32 = 2;
+
+/// A spider's hit points, which attacks wear down until it dies at zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct SpiderHealth {
+    pub hp: u32,
+    pub max_hp: u32,
+}
+
+impl SpiderHealth {
+    pub fn full(level: u32) -> Self {
+        let max_hp = level * SPIDER_HP_PER_LEVEL;
+        Self { hp: max_hp, max_hp }
+    }
+
+    /// How much health is left, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        self.hp as f32 / self.max_hp.max(1) as f32
+    }
+}
+
+pub struct SpiderSpawnTimer(pub Timer);
+
+/// Counts down to a web spitter's next web.
+#[derive(Component)]
+pub struct WebTimer(pub Timer);
+
+/// A slime stuck in a web, which holds it in place until the timer runs out.
+#[derive(Component)]
+pub struct Webbed(pub Timer);
+
+/// The "LVL n color" label, a child of its spider.
+#[derive(Component)]
+pub struct SpiderText;
+
+pub struct SpawnSpiderEvent {
+    pub spider: Spider,
+    pub position: Vec2,
+    /// Makes the spider a boss, which starts out with the health of its first phase.
+    pub boss: Option<Boss>,
+}
+
+fn spider_spawner(
+    mut commands: Commands,
+    fonts: Res<FontResources>,
+    spider_resources: Res<SpiderResources>,
+    mut events: EventReader<SpawnSpiderEvent>,
+) {
+    for ev in events.iter() {
+        let scale = 1. + ev.spider.level as f32;
+        let radius_px = scale * SPIDER_RADIUS_PX;
+        let health = ev
+            .boss
+            .as_ref()
+            .map_or(SpiderHealth::full(ev.spider.level), Boss::phase_health);
+        let tint = if ev.boss.is_some() {
+            BOSS_TINT
+        } else {
+            ev.spider.kind.tint()
+        };
+        let mut spider = commands.spawn();
+        if ev.spider.kind == SpiderKind::WebSpitter {
+            spider.insert(WebTimer(Timer::from_seconds(WEB_INTERVAL_SECS, true)));
+        }
+        if let Some(boss) = &ev.boss {
+            spider.insert(boss.clone());
+        }
+        spider
+            .insert_bundle(SpatialBundle {
+                transform: Transform::from_translation(ev.position.extend(0.)),
+                ..default()
+            })
+            .insert(ev.spider)
+            .insert(health)
+            .insert(Pathing::new(ev.position.y))
+            .insert(Interactable {
+                activation_radius: radius_px,
+            })
+            .insert(HoverActive(false))
+            // rapier components
+            .insert(RigidBody::KinematicVelocityBased)
+            .insert(Collider::ball(radius_px))
+            .insert(LockedAxes::ROTATION_LOCKED)
+            .insert(CollisionGroups::default())
+            .insert(Restitution::coefficient(0.5))
+            .insert(Friction::new(0.0))
+            .insert(Velocity::linear(Vec2::new(-ev.spider.speed, 0.)))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(SpriteSheetBundle {
+                        sprite: TextureAtlasSprite {
+                            color: tint,
+                            ..default()
+                        },
+                        texture_atlas: spider_resources.texture_atlas.clone(),
+                        transform: Transform::from_translation(Vec3::new(-1., 0., MAIN_LAYER))
+                            .with_scale(Vec3::splat(scale))
+                            .with_rotation(Quat::from_axis_angle(
+                                Vec3::Z,
+                                -std::f32::consts::FRAC_PI_2,
+                            )),
+                        ..default()
+                    })
+                    .insert(AnimationTimer(Timer::from_seconds(0.2, true)))
+                    .insert(SpriteAnimation::spider_walk());
+                let font = fonts.game.clone();
+                let font_size = 24. + ev.spider.level as f32 * 8.;
+                let lvl_text = TextSection {
+                    value: "LVL ".to_owned(),
+                    style: TextStyle {
+                        font: font.clone(),
+                        font_size: 12. + ev.spider.level as f32 * 4.,
+                        color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+                    },
+                };
+                let number_text = TextSection {
+                    value: format!("{} ", ev.spider.level),
+                    style: TextStyle {
+                        font: font.clone(),
+                        font_size,
+                        color: Color::WHITE,
+                    },
+                };
+                let color_text = TextSection {
+                    value: ev.spider.weakness.name().to_owned(),
+                    style: TextStyle {
+                        font: font.clone(),
+                        font_size,
+                        color: ev.spider.weakness.color(),
+                    },
+                };
+                let hp_text = TextSection {
+                    value: hp_label(&health),
+                    style: TextStyle {
+                        font: font.clone(),
+                        font_size: 12. + ev.spider.level as f32 * 4.,
+                        color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+                    },
+                };
+                let kind_text = TextSection {
+                    value: match ev.spider.kind {
+                        _ if ev.boss.is_some() => " boss".to_owned(),
+                        SpiderKind::Common => String::new(),
+                        kind => format!(" {}", kind.name()),
+                    },
+                    style: TextStyle {
+                        font,
+                        font_size: 12. + ev.spider.level as f32 * 4.,
+                        color: tint,
+                    },
+                };
+                parent
+                    .spawn_bundle(Text2dBundle {
+                        text: Text::from_sections([
+                            lvl_text,
+                            number_text,
+                            color_text,
+                            hp_text,
+                            kind_text,
+                        ])
+                        .with_alignment(TextAlignment::CENTER),
+                        transform: Transform::from_xyz(0., radius_px, LABEL_LAYER),
+                        ..default()
+                    })
+                    .insert(SpiderText);
+            });
+    }
+}
+
+fn hp_label(health: &SpiderHealth) -> String {
+    format!("\nHP {}/{}", health.hp, health.max_hp)
+}
+
+/// Shrinks hurt spiders and keeps their labels' hit points up to date, along with the weakness
+/// of bosses that have moved on to their next phase.
+fn show_spider_health(
+    spider_query: Query<(&Spider, &SpiderHealth, &Children), Changed<SpiderHealth>>,
+    mut text_query: Query<&mut Text, With<SpiderText>>,
+    mut sprite_query: Query<&mut Transform, With<TextureAtlasSprite>>,
+) {
+    for (spider, health, children) in &spider_query {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.sections[2].value = spider.weakness.name().to_owned();
+                text.sections[2].style.color = spider.weakness.color();
+                text.sections[3].value = hp_label(health);
+            }
+            if let Ok(mut transform) = sprite_query.get_mut(child) {
+                let scale = (1. + spider.level as f32) * (0.6 + 0.4 * health.fraction());
+                transform.scale = Vec3::splat(scale);
+            }
+        }
+    }
+}
+
+/// Webs up every slime near a web spitter whenever its timer comes round, unless it's being
+/// dragged.
+fn spit_webs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut spitter_query: Query<(&Transform, &mut WebTimer), With<Spider>>,
+    mut slime_query: Query<
+        (Entity, &Transform, &DragActive, &mut Velocity, &Children),
+        (With<Slime>, Without<Webbed>),
+    >,
+    mut sprite_query: Query<&mut TextureAtlasSprite>,
+) {
+    for (spitter_transform, mut timer) in &mut spitter_query {
+        if !timer.0.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let spitter_position = spitter_transform.translation.truncate();
+        for (entity, transform, drag_active, mut velocity, children) in &mut slime_query {
+            if drag_active.0
+                || transform.translation.truncate().distance(spitter_position) > WEB_RADIUS_PX
+            {
+                continue;
+            }
+            *velocity = Velocity::zero();
+            commands
+                .entity(entity)
+                .insert(Webbed(Timer::from_seconds(WEB_DURATION_SECS, false)));
+            for &child in children.iter() {
+                if let Ok(mut sprite) = sprite_query.get_mut(child) {
+                    sprite.color = Color::rgba(0.8, 0.8, 0.8, 0.6);
+                }
+            }
+        }
+    }
+}
+
+/// Holds webbed slimes still, and lets them go once their web wears off.
+fn wear_off_webs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut webbed_query: Query<(Entity, &mut Webbed, &mut Velocity, &Children)>,
+    mut sprite_query: Query<&mut TextureAtlasSprite>,
+) {
+    for (entity, mut webbed, mut velocity, children) in &mut webbed_query {
+        *velocity = Velocity::zero();
+        if webbed.0.tick(time.delta()).just_finished() {
+            commands.entity(entity).remove::<Webbed>();
+            for &child in children.iter() {
+                if let Ok(mut sprite) = sprite_query.get_mut(child) {
+                    sprite.color = Color::WHITE;
+                }
+            }
+        }
+    }
+}
+
+fn setup_spider_spawn_timer(mut commands: Commands, waves: Res<WaveConfig>) {
+    commands.insert_resource(SpiderSpawnTimer(Timer::new(waves.interval(0), false)));
+}
+
+fn spider_spawn_timer(
+    time: Res<Time>,
+    waves: Res<WaveConfig>,
+    mut score: ResMut<ScoreResource>,
+    mut game_rng: ResMut<GameRng>,
+    mut timer: ResMut<SpiderSpawnTimer>,
+    mut events: EventWriter<SpawnSpiderEvent>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let rng = game_rng.stream(RngStream::Spiders);
+    let spawned = score.spiders_spawned;
+    if let Some(wave) = waves.boss(spawned) {
+        let boss = Boss::from_wave(wave, rng);
+        score.spiders_spawned += 1;
+        timer.0 = Timer::new(waves.interval(score.spiders_spawned), false);
+        events.send(SpawnSpiderEvent {
+            spider: boss.spider(wave.level, wave.speed),
+            position: Vec2::new(
+                PLAYFIELD_WIDTH / 2. + (1. + wave.level as f32) * SPIDER_RADIUS_PX,
+                0.,
+            ),
+            boss: Some(boss),
+        });
+        return;
+    }
+    let level = waves.level(spawned, rng);
+    let kind = waves.kind(spawned, rng);
+    let movement = waves.movement(spawned, rng);
+    score.spiders_spawned += 1;
+    timer.0 = Timer::new(waves.interval(score.spiders_spawned), false);
+    events.send(SpawnSpiderEvent {
+        spider: Spider {
+            level,
+            weakness: SlimeColor::ALL[rng.gen_range(0..8)],
+            speed: waves.speed(spawned) * kind.speed_multiplier(),
+            kind,
+            movement,
+        },
+        position: Vec2::new(
+            PLAYFIELD_WIDTH / 2. + (1. + level as f32) * SPIDER_RADIUS_PX,
+            rng.gen_range((-PLAYFIELD_HEIGHT / 3.)..PLAYFIELD_HEIGHT / 3.),
+        ),
+        boss: None,
+    });
+}
+
+fn despawn_spider_text(mut commands: Commands, query: Query<Entity, With<SpiderText>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn despawn_spiders(mut commands: Commands, query: Query<Entity, With<Spider>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
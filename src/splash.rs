@@ -0,0 +1,258 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    combine::{attacker_remains, hatch, spider_damage},
+    interaction::circle_shape,
+    slime::Slime,
+    spider::{Spider, SpiderKind, ARMORED_MIN_SLIME_SIZE},
+    AppState, GameplaySystem, SHAPE_LAYER,
+};
+
+/// Seconds the ring a splash draws takes to spread out and fade.
+pub const SPLASH_RING_SECS: f32 = 0.4;
+
+/// Lets big slimes that kill a spider of their color splash everything around them and bounce
+/// their halves into the spiders nearby, once [`SplashConfig::enabled`] is switched on.
+///
+/// [`combine`](crate::combine) works out the chain with [`splash`] and applies it; this plugin
+/// only draws it.
+pub struct SplashPlugin;
+
+impl Plugin for SplashPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SplashConfig>()
+            .add_event::<SplashEvent>()
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(draw_splashes.after(GameplaySystem::Combine))
+                    .with_system(spread_splashes.after(draw_splashes)),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::GameOver).with_system(despawn_splashes));
+    }
+}
+
+/// Whether kills splash, and how far and hard.
+///
+/// It's off unless the player asks for it, and is fixed for the length of a run so that
+/// replays play out the same.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplashConfig {
+    pub enabled: bool,
+    /// The smallest slime whose kills splash. It also has to match the spider's weakness.
+    pub min_size: u32,
+    /// How far a splash reaches, in pixels.
+    pub radius_px: f32,
+    /// How much of its damage a splash has lost by the edge of its radius, from 0 for none of
+    /// it to 1 for all of it.
+    pub falloff: f32,
+    /// How far the halves of a splashing slime look for another spider to bounce into, in
+    /// pixels.
+    pub bounce_px: f32,
+}
+
+impl Default for SplashConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_size: 3,
+            radius_px: 120.,
+            falloff: 0.5,
+            bounce_px: 150.,
+        }
+    }
+}
+
+impl SplashConfig {
+    /// Whether `slime` killing `spider` makes a splash.
+    pub fn splashes(&self, slime: &Slime, spider: &Spider) -> bool {
+        slime.size >= self.min_size && slime.color == spider.weakness
+    }
+
+    /// How many hit points a splash from `slime` takes off `spider`, `distance` pixels away.
+    ///
+    /// That's the slime's size where it lands, fading by [`SplashConfig::falloff`] towards the
+    /// edge and rounded to the nearest hit point. Armor keeps out the splashes of slimes too
+    /// small to get through it.
+    pub fn damage(&self, slime: &Slime, spider: &Spider, distance: f32) -> u32 {
+        if distance > self.radius_px
+            || (spider.kind == SpiderKind::Armored && slime.size < ARMORED_MIN_SLIME_SIZE)
+        {
+            return 0;
+        }
+        let strength = 1. - self.falloff * distance / self.radius_px;
+        (slime.size as f32 * strength).round() as u32
+    }
+}
+
+/// A spider that a splash can reach: anything but a boss, which shrugs splashes off.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplashTarget {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub spider: Spider,
+    pub hp: u32,
+}
+
+/// How a chain of splashes played out.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SplashOutcome {
+    /// The hit points each target that was hit lost, in the same order as the targets.
+    pub damage: Vec<(Entity, u32)>,
+    /// The targets that were killed, in the order they died.
+    pub killed: Vec<SplashTarget>,
+    /// Where every splash landed.
+    pub splashes: Vec<Vec2>,
+    /// The slimes left over once the chain is done, and where they ended up.
+    pub landed: Vec<(Slime, Vec2)>,
+    /// Spiders hatched by brood mothers killed along the way, and where.
+    pub brood: Vec<(Spider, Vec2)>,
+}
+
+impl SplashOutcome {
+    fn kill(&mut self, target: &SplashTarget) {
+        self.killed.push(*target);
+        self.brood.extend(
+            hatch(&target.spider)
+                .into_iter()
+                .map(|spider| (spider, target.position)),
+        );
+    }
+}
+
+/// Plays out `slime` killing `spider` at `position`, with `targets` around it, where `slime` is
+/// what it attacked as and `remains` what's left of it.
+///
+/// If the kill [`SplashConfig::splashes`], every target within reach takes
+/// [`SplashConfig::damage`], and then each of the `remains` bounces into the nearest target
+/// still standing within [`SplashConfig::bounce_px`] and attacks it as if it had been dropped
+/// there. Bounces that kill can splash in turn, and so on until the slimes are too small or
+/// run out of spiders. Halves with nothing to bounce into land where they are.
+pub fn splash(
+    config: &SplashConfig,
+    slime: &Slime,
+    remains: Vec<Slime>,
+    spider: &Spider,
+    position: Vec2,
+    targets: &[SplashTarget],
+) -> SplashOutcome {
+    let mut hp: Vec<u32> = targets.iter().map(|target| target.hp).collect();
+    let mut outcome = SplashOutcome::default();
+    let mut kills = vec![(*slime, remains, *spider, position)];
+    while let Some((slime, remains, spider, position)) = kills.pop() {
+        if !config.splashes(&slime, &spider) {
+            outcome
+                .landed
+                .extend(remains.into_iter().map(|slime| (slime, position)));
+            continue;
+        }
+        outcome.splashes.push(position);
+        for (index, target) in targets.iter().enumerate() {
+            if hp[index] == 0 {
+                continue;
+            }
+            let damage = config
+                .damage(&slime, &target.spider, target.position.distance(position))
+                .min(hp[index]);
+            hp[index] -= damage;
+            if hp[index] == 0 {
+                outcome.kill(target);
+            }
+        }
+        for half in remains {
+            let nearest = targets
+                .iter()
+                .enumerate()
+                .filter(|&(index, target)| {
+                    hp[index] > 0 && target.position.distance(position) <= config.bounce_px
+                })
+                .min_by(|(_, a), (_, b)| {
+                    a.position
+                        .distance_squared(position)
+                        .total_cmp(&b.position.distance_squared(position))
+                });
+            let Some((index, target)) = nearest else {
+                outcome.landed.push((half, position));
+                continue;
+            };
+            let damage = spider_damage(&target.spider, &half).min(hp[index]);
+            hp[index] -= damage;
+            if hp[index] == 0 {
+                outcome.kill(target);
+                kills.push((
+                    half,
+                    attacker_remains(&half, damage),
+                    target.spider,
+                    target.position,
+                ));
+            } else {
+                outcome.landed.extend(
+                    attacker_remains(&half, damage)
+                        .into_iter()
+                        .map(|slime| (slime, target.position)),
+                );
+            }
+        }
+    }
+    outcome.damage = targets
+        .iter()
+        .zip(hp)
+        .filter(|(target, hp)| target.hp > *hp)
+        .map(|(target, hp)| (target.entity, target.hp - hp))
+        .collect();
+    outcome
+}
+
+/// Sent for every splash, so that it can be drawn.
+pub struct SplashEvent {
+    pub position: Vec2,
+    pub color: Color,
+}
+
+/// The ring a splash spreads out in.
+#[derive(Component)]
+pub struct SplashRing(pub Timer);
+
+fn draw_splashes(
+    mut commands: Commands,
+    config: Res<SplashConfig>,
+    mut events: EventReader<SplashEvent>,
+) {
+    for ev in events.iter() {
+        commands
+            .spawn_bundle(circle_shape(config.radius_px, ev.color))
+            .insert(
+                Transform::from_translation(ev.position.extend(SHAPE_LAYER)).with_scale(Vec3::ZERO),
+            )
+            .insert(SplashRing(Timer::from_seconds(SPLASH_RING_SECS, false)));
+    }
+}
+
+fn spread_splashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut ring_query: Query<(Entity, &mut SplashRing, &mut Transform, &mut DrawMode)>,
+) {
+    for (entity, mut ring, mut transform, mut draw_mode) in &mut ring_query {
+        if ring.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let t = ring.0.percent();
+        transform.scale = Vec3::splat(t);
+        if let DrawMode::Outlined {
+            ref mut outline_mode,
+            ..
+        } = *draw_mode
+        {
+            outline_mode.color.set_a(1. - t);
+        }
+    }
+}
+
+fn despawn_splashes(mut commands: Commands, query: Query<Entity, With<SplashRing>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::{Collider, CollisionGroups, Damping, Sensor};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assets::TileResources,
+    rng::{GameRng, RngStream},
+    slime::{Slime, SLIME_LINEAR_DAMPING},
+    AppState, GameplaySystem, TERRAIN_LAYER,
+};
+
+/// The size of a tile of the background, which obstacles are laid out on, in pixels.
+pub const TILE_PX: f32 = 64.;
+/// How many obstacles a [`TerrainLayout::Random`] field gets.
+pub const RANDOM_OBSTACLE_COUNT: usize = 5;
+/// The tiles random obstacles may cover: clear of the plants' scent, where spiders need a
+/// straight run at the garden, and of the right edge, where they come in.
+pub const RANDOM_COLUMNS: (i32, i32) = (-3, 6);
+pub const RANDOM_ROWS: (i32, i32) = (-4, 4);
+
+/// Rocks, bushes and ponds that stand in the way of slimes and spiders, laid out anew at the
+/// start of each run from the [`TerrainLayout`].
+///
+/// Slimes are dynamic bodies, so rocks stop them with a solid collider; spiders are kinematic,
+/// so [`pathing`](crate::pathing) steers them around whatever [`TerrainKind::blocks_spiders`].
+pub struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TerrainLayout>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(spawn_terrain.after(GameplaySystem::StartRun)),
+            )
+            .add_system_set(SystemSet::on_update(AppState::InGame).with_system(slow_slimes))
+            .add_system_set(SystemSet::on_exit(AppState::GameOver).with_system(despawn_terrain));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TerrainKind {
+    /// Nothing gets through.
+    Rock,
+    /// Slimes and spiders push through at half speed.
+    Bush,
+    /// Spiders walk around, and slimes wade through slowly.
+    Pond,
+}
+
+impl TerrainKind {
+    pub const ALL: [Self; 3] = [Self::Rock, Self::Bush, Self::Pond];
+
+    pub fn blocks_slimes(&self) -> bool {
+        *self == Self::Rock
+    }
+
+    pub fn blocks_spiders(&self) -> bool {
+        matches!(self, Self::Rock | Self::Pond)
+    }
+
+    /// What the speed of whatever gets through is multiplied by.
+    pub fn slowdown(&self) -> f32 {
+        match self {
+            Self::Rock => 1.,
+            Self::Bush => 0.5,
+            Self::Pond => 0.3,
+        }
+    }
+
+    /// The tiles of the grass tileset an obstacle of this kind is laid with, one picked at
+    /// random for each tile it covers.
+    ///
+    /// The tileset has no bushes or water, so bushes are laid with its flowery grass and ponds
+    /// with its stone slabs, and [`TerrainKind::tint`] sets them apart.
+    fn tile_indices(&self) -> &'static [usize] {
+        match self {
+            Self::Rock | Self::Pond => &[32, 33, 34, 40, 41, 42],
+            Self::Bush => &[5, 6, 7, 13, 14, 15],
+        }
+    }
+
+    fn tint(&self) -> Color {
+        match self {
+            Self::Rock => Color::WHITE,
+            Self::Bush => Color::rgb(0.45, 0.75, 0.3),
+            Self::Pond => Color::rgb(0.45, 0.7, 1.),
+        }
+    }
+}
+
+/// An obstacle covering `width` by `height` tiles, with its bottom left tile at `column` and
+/// `row` counted from the tile in the middle of the field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Component)]
+pub struct Obstacle {
+    pub kind: TerrainKind,
+    pub column: i32,
+    pub row: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Obstacle {
+    pub fn center(&self) -> Vec2 {
+        TILE_PX
+            * Vec2::new(
+                self.column as f32 + (self.width as f32 - 1.) / 2.,
+                self.row as f32 + (self.height as f32 - 1.) / 2.,
+            )
+    }
+
+    pub fn half_size(&self) -> Vec2 {
+        TILE_PX / 2. * Vec2::new(self.width as f32, self.height as f32)
+    }
+
+    /// Whether a circle of `radius` at `position` overlaps the obstacle.
+    pub fn overlaps(&self, position: Vec2, radius: f32) -> bool {
+        let offset = position - self.center();
+        let nearest = offset.clamp(-self.half_size(), self.half_size());
+        offset.distance_squared(nearest) < radius * radius
+    }
+
+    /// Whether this and `other` cover columns within `gap` tiles of each other.
+    fn shares_columns(&self, other: &Obstacle, gap: i32) -> bool {
+        self.column - gap < other.column + other.width as i32
+            && other.column - gap < self.column + self.width as i32
+    }
+
+    /// Whether this and `other` come within `gap` tiles of each other.
+    fn crowds(&self, other: &Obstacle, gap: i32) -> bool {
+        self.shares_columns(other, gap)
+            && self.row - gap < other.row + other.height as i32
+            && other.row - gap < self.row + self.height as i32
+    }
+}
+
+/// Where a run's obstacles go. The field is open unless the player asks for obstacles.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum TerrainLayout {
+    /// No obstacles at all.
+    #[default]
+    Open,
+    /// [`RANDOM_OBSTACLE_COUNT`] obstacles placed from the run's seed; see [`random_obstacles`].
+    Random,
+    /// Exactly these obstacles, as a map lays them out.
+    Map(Vec<Obstacle>),
+}
+
+impl TerrainLayout {
+    pub fn from_ron(ron: &str) -> ron::Result<Self> {
+        ron::from_str(ron)
+    }
+
+    /// The obstacles to put on the field, drawing from `rng` if they're random.
+    pub fn obstacles(&self, rng: &mut impl Rng) -> Vec<Obstacle> {
+        match self {
+            Self::Open => Vec::new(),
+            Self::Random => random_obstacles(rng),
+            Self::Map(obstacles) => obstacles.clone(),
+        }
+    }
+}
+
+/// Scatters up to [`RANDOM_OBSTACLE_COUNT`] obstacles of one or two tiles a side across
+/// [`RANDOM_COLUMNS`] and [`RANDOM_ROWS`].
+///
+/// Obstacles keep a tile clear of each other, and no two that block spiders share a column,
+/// so there's always a way past.
+pub fn random_obstacles(rng: &mut impl Rng) -> Vec<Obstacle> {
+    let mut obstacles: Vec<Obstacle> = Vec::new();
+    // a crowded field can run out of room, so give up after a while rather than loop forever.
+    for _ in 0..RANDOM_OBSTACLE_COUNT * 10 {
+        if obstacles.len() == RANDOM_OBSTACLE_COUNT {
+            break;
+        }
+        let kind = TerrainKind::ALL[rng.gen_range(0..TerrainKind::ALL.len())];
+        let width = rng.gen_range(1..=2);
+        let height = rng.gen_range(1..=2);
+        let candidate = Obstacle {
+            kind,
+            column: rng.gen_range(RANDOM_COLUMNS.0..=RANDOM_COLUMNS.1 + 1 - width as i32),
+            row: rng.gen_range(RANDOM_ROWS.0..=RANDOM_ROWS.1 + 1 - height as i32),
+            width,
+            height,
+        };
+        let crowded = obstacles.iter().any(|other| {
+            candidate.crowds(other, 1)
+                || (kind.blocks_spiders()
+                    && other.kind.blocks_spiders()
+                    && candidate.shares_columns(other, 0))
+        });
+        if !crowded {
+            obstacles.push(candidate);
+        }
+    }
+    obstacles
+}
+
+fn spawn_terrain(
+    mut commands: Commands,
+    layout: Res<TerrainLayout>,
+    tile_resources: Res<TileResources>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = game_rng.stream(RngStream::Terrain);
+    for obstacle in layout.obstacles(rng) {
+        let half_size = obstacle.half_size();
+        let mut entity = commands.spawn_bundle(SpatialBundle::from_transform(
+            Transform::from_translation(obstacle.center().extend(TERRAIN_LAYER)),
+        ));
+        entity
+            .insert(obstacle)
+            .insert(Collider::cuboid(half_size.x, half_size.y))
+            .insert(CollisionGroups::default());
+        if !obstacle.kind.blocks_slimes() {
+            entity.insert(Sensor);
+        }
+        // the tiles are drawn at twice their size, like the background's.
+        let indices = obstacle.kind.tile_indices();
+        entity.with_children(|parent| {
+            for column in 0..obstacle.width {
+                for row in 0..obstacle.height {
+                    let offset =
+                        TILE_PX * Vec2::new(column as f32, row as f32) - half_size + TILE_PX / 2.;
+                    parent.spawn_bundle(SpriteSheetBundle {
+                        sprite: TextureAtlasSprite {
+                            index: indices[rng.gen_range(0..indices.len())],
+                            color: obstacle.kind.tint(),
+                            ..default()
+                        },
+                        texture_atlas: tile_resources.texture_atlas.clone(),
+                        transform: Transform::from_translation(offset.extend(0.))
+                            * Transform::from_scale(Vec3::splat(2.)),
+                        ..default()
+                    });
+                }
+            }
+        });
+    }
+}
+
+/// Bogs down slimes that are in a bush or a pond by damping them harder, so that whatever
+/// pushes them gets them less far.
+fn slow_slimes(
+    obstacle_query: Query<&Obstacle>,
+    mut slime_query: Query<(&Slime, &Transform, &mut Damping)>,
+) {
+    for (slime, transform, mut damping) in &mut slime_query {
+        let position = transform.translation.truncate();
+        let radius = slime.radius_px();
+        let slowdown = obstacle_query
+            .iter()
+            .filter(|obstacle| obstacle.overlaps(position, radius))
+            .map(|obstacle| obstacle.kind.slowdown())
+            .fold(1., f32::min);
+        let linear_damping = SLIME_LINEAR_DAMPING / slowdown;
+        if damping.linear_damping != linear_damping {
+            damping.linear_damping = linear_damping;
+        }
+    }
+}
+
+fn despawn_terrain(mut commands: Commands, query: Query<Entity, With<Obstacle>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,399 @@
+use std::time::Duration;
+
+use bevy::{
+    asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+};
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{pathing::SpiderMovement, spider::SpiderKind};
+
+/// The difficulty ladder: which spiders show up, how fast, and how often.
+///
+/// Every value is looked up by how many spiders spawned before the one being asked about. The
+/// game reads this as a resource, which starts out as [`WaveConfig::default`] and is replaced by
+/// `assets/waves.ron` once [`WavesPlugin`] has loaded it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypeUuid)]
+#[uuid = "5e1f3e0c-8a43-4c36-9d0b-3b7f6a52f1a4"]
+pub struct WaveConfig {
+    /// Which levels a spider can have. The last step whose `from_spawned` has been reached
+    /// applies.
+    pub levels: Vec<LevelStep>,
+    /// Seconds until the next spider.
+    pub interval: PerSpawn,
+    /// How fast a spider walks, in pixels per second, before its kind's multiplier.
+    pub speed: PerSpawn,
+    /// How likely each kind of spider is. Kinds only show up once `from_spawned` has been
+    /// reached.
+    #[serde(default = "default_kinds")]
+    pub kinds: Vec<KindWeight>,
+    /// How likely each way of moving is, in the same way as `kinds`.
+    #[serde(default = "default_movements")]
+    pub movements: Vec<MovementWeight>,
+    /// Bosses that take the place of the spider spawned at their milestone.
+    #[serde(default = "default_bosses")]
+    pub bosses: Vec<BossWave>,
+}
+
+/// A boss encounter, which replaces the ordinary spider that would have been spawned once
+/// `at_spawned` spiders have.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BossWave {
+    pub at_spawned: u32,
+    pub level: u32,
+    /// How fast it walks, in pixels per second.
+    pub speed: f32,
+    /// How many weaknesses it has, each of which has to be worn down in turn.
+    pub phases: usize,
+    pub hp_per_phase: u32,
+    /// How many spiders it summons whenever it moves on to its next phase.
+    pub minions: u32,
+    pub minion_level: u32,
+}
+
+fn default_bosses() -> Vec<BossWave> {
+    vec![
+        BossWave {
+            at_spawned: 20,
+            level: 7,
+            speed: 30.,
+            phases: 3,
+            hp_per_phase: 10,
+            minions: 2,
+            minion_level: 2,
+        },
+        BossWave {
+            at_spawned: 40,
+            level: 8,
+            speed: 36.,
+            phases: 4,
+            hp_per_phase: 12,
+            minions: 3,
+            minion_level: 3,
+        },
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MovementWeight {
+    pub movement: SpiderMovement,
+    pub weight: u32,
+    #[serde(default)]
+    pub from_spawned: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KindWeight {
+    pub kind: SpiderKind,
+    pub weight: u32,
+    #[serde(default)]
+    pub from_spawned: u32,
+}
+
+fn default_movements() -> Vec<MovementWeight> {
+    let weight = |movement, weight, from_spawned| MovementWeight {
+        movement,
+        weight,
+        from_spawned,
+    };
+    vec![
+        weight(SpiderMovement::Straight, 6, 0),
+        weight(
+            SpiderMovement::SineWave {
+                amplitude: 60.,
+                period: 3.,
+            },
+            2,
+            3,
+        ),
+        weight(
+            SpiderMovement::ZigZag {
+                amplitude: 50.,
+                period: 2.,
+            },
+            2,
+            5,
+        ),
+        weight(
+            SpiderMovement::LaneChange {
+                lane_width: 120.,
+                interval: 3.,
+            },
+            1,
+            7,
+        ),
+        weight(
+            SpiderMovement::Charge {
+                multiplier: 3.,
+                interval: 4.,
+                duration: 1.,
+            },
+            1,
+            9,
+        ),
+        weight(SpiderMovement::Avoid { radius: 150. }, 1, 12),
+    ]
+}
+
+fn default_kinds() -> Vec<KindWeight> {
+    let weight = |kind, weight, from_spawned| KindWeight {
+        kind,
+        weight,
+        from_spawned,
+    };
+    vec![
+        weight(SpiderKind::Common, 6, 0),
+        weight(SpiderKind::Scout, 2, 2),
+        weight(SpiderKind::Armored, 1, 6),
+        weight(SpiderKind::WebSpitter, 1, 8),
+        weight(SpiderKind::BroodMother, 1, 10),
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelStep {
+    pub from_spawned: u32,
+    /// The lowest level a spider can have, inclusive.
+    pub min: u32,
+    /// The highest level a spider can have, inclusive.
+    pub max: u32,
+}
+
+/// A value that starts at `base` and changes by `per_spawn` with every spider, within
+/// `min..=max`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerSpawn {
+    pub base: f32,
+    pub per_spawn: f32,
+    #[serde(default = "no_min")]
+    pub min: f32,
+    #[serde(default = "no_max")]
+    pub max: f32,
+}
+
+fn no_min() -> f32 {
+    f32::NEG_INFINITY
+}
+
+fn no_max() -> f32 {
+    f32::INFINITY
+}
+
+impl PerSpawn {
+    pub fn at(&self, spawned: u32) -> f32 {
+        (self.base + self.per_spawn * spawned as f32).clamp(self.min, self.max)
+    }
+}
+
+impl Default for WaveConfig {
+    /// The same ladder as `assets/waves.ron` ships with.
+    fn default() -> Self {
+        let step = |from_spawned, min, max| LevelStep {
+            from_spawned,
+            min,
+            max,
+        };
+        Self {
+            levels: vec![
+                step(0, 2, 2),
+                step(2, 3, 3),
+                step(3, 2, 4),
+                step(8, 2, 5),
+                step(11, 3, 5),
+                step(15, 4, 5),
+                step(20, 5, 5),
+            ],
+            interval: PerSpawn {
+                base: 5.,
+                per_spawn: -0.1,
+                min: 1.,
+                max: no_max(),
+            },
+            speed: PerSpawn {
+                base: 62.,
+                per_spawn: 2.,
+                min: no_min(),
+                max: no_max(),
+            },
+            kinds: default_kinds(),
+            movements: default_movements(),
+            bosses: default_bosses(),
+        }
+    }
+}
+
+impl WaveConfig {
+    pub fn from_ron(ron: &str) -> Result<Self, bevy::asset::Error> {
+        let config: Self = ron::from_str(ron)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), bevy::asset::Error> {
+        if self.levels.is_empty() {
+            return Err(bevy::asset::Error::msg(
+                "waves need at least one level step",
+            ));
+        }
+        for step in &self.levels {
+            if step.min > step.max {
+                return Err(bevy::asset::Error::msg(format!(
+                    "level step from {} spawned has min {} above max {}",
+                    step.from_spawned, step.min, step.max
+                )));
+            }
+        }
+        for boss in &self.bosses {
+            if boss.phases == 0 || boss.hp_per_phase == 0 {
+                return Err(bevy::asset::Error::msg(format!(
+                    "boss at {} spawned needs at least one phase and hit point",
+                    boss.at_spawned
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The boss that comes instead of the spider spawned after `spawned` others, if any.
+    pub fn boss(&self, spawned: u32) -> Option<&BossWave> {
+        self.bosses.iter().find(|boss| boss.at_spawned == spawned)
+    }
+
+    /// Which wave the spider spawned after `spawned` others belongs to, counting each level
+    /// step that has been reached as the start of a new one.
+    pub fn wave(&self, spawned: u32) -> usize {
+        self.levels
+            .iter()
+            .filter(|step| step.from_spawned <= spawned)
+            .count()
+    }
+
+    /// Picks the level of the next spider. Steps with a single level don't draw from `rng`.
+    pub fn level(&self, spawned: u32, rng: &mut impl Rng) -> u32 {
+        let step = self
+            .levels
+            .iter()
+            .rev()
+            .find(|step| step.from_spawned <= spawned)
+            .unwrap_or(&self.levels[0]);
+        if step.min == step.max {
+            step.min
+        } else {
+            rng.gen_range(step.min..=step.max)
+        }
+    }
+
+    /// Picks the kind of the next spider, weighted among the kinds that have shown up by now.
+    /// Common spiders are all there is before any kind has.
+    pub fn kind(&self, spawned: u32, rng: &mut impl Rng) -> SpiderKind {
+        let kinds = self
+            .kinds
+            .iter()
+            .map(|kind| (kind.kind, kind.weight, kind.from_spawned));
+        pick_weighted(kinds, spawned, rng).unwrap_or_default()
+    }
+
+    /// Picks how the next spider moves, in the same way as [`WaveConfig::kind`].
+    pub fn movement(&self, spawned: u32, rng: &mut impl Rng) -> SpiderMovement {
+        let movements = self
+            .movements
+            .iter()
+            .map(|movement| (movement.movement, movement.weight, movement.from_spawned));
+        pick_weighted(movements, spawned, rng).unwrap_or_default()
+    }
+
+    pub fn interval(&self, spawned: u32) -> Duration {
+        Duration::from_secs_f32(self.interval.at(spawned).max(0.))
+    }
+
+    pub fn speed(&self, spawned: u32) -> f32 {
+        self.speed.at(spawned)
+    }
+}
+
+/// Picks one of the `(choice, weight, from_spawned)` that have shown up by `spawned`, drawing
+/// from `rng` only if there's more than one.
+fn pick_weighted<T: Copy>(
+    choices: impl Iterator<Item = (T, u32, u32)>,
+    spawned: u32,
+    rng: &mut impl Rng,
+) -> Option<T> {
+    let available: Vec<_> = choices
+        .filter(|&(_, weight, from_spawned)| from_spawned <= spawned && weight > 0)
+        .collect();
+    match available[..] {
+        [] => None,
+        [(only, ..)] => Some(only),
+        _ => {
+            let index = WeightedIndex::new(available.iter().map(|&(_, weight, _)| weight))
+                .expect("weights are positive");
+            Some(available[index.sample(rng)].0)
+        }
+    }
+}
+
+/// Loads [`WaveConfig`] from `assets/waves.ron`, and again whenever the asset server sees the
+/// file change.
+///
+/// Needs the asset server, so headless games stick to the [`WaveConfig`] resource they were
+/// given.
+pub struct WavesPlugin;
+
+impl Plugin for WavesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<WaveConfig>()
+            .init_asset_loader::<WaveConfigLoader>()
+            .add_startup_system(load_waves)
+            .add_system(apply_waves);
+    }
+}
+
+#[derive(Default)]
+pub struct WaveConfigLoader;
+
+impl AssetLoader for WaveConfigLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let config = WaveConfig::from_ron(std::str::from_utf8(bytes)?)?;
+            load_context.set_default_asset(LoadedAsset::new(config));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["waves.ron"]
+    }
+}
+
+struct WavesHandle(Handle<WaveConfig>);
+
+fn load_waves(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(WavesHandle(asset_server.load("waves.ron")));
+}
+
+fn apply_waves(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<WaveConfig>>,
+    handle: Res<WavesHandle>,
+    configs: Res<Assets<WaveConfig>>,
+) {
+    for ev in events.iter() {
+        if let AssetEvent::Created { handle: changed } | AssetEvent::Modified { handle: changed } =
+            ev
+        {
+            if *changed != handle.0 {
+                continue;
+            }
+            if let Some(config) = configs.get(changed) {
+                info!("loaded waves");
+                commands.insert_resource(config.clone());
+            }
+        }
+    }
+}
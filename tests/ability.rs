@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    ability::{
+        AbilityCircle, Frozen, SlimeAbility, AURA_RADIUS_PX, BURN_INTERVAL_SECS,
+        FREEZE_INTERVAL_SECS, GROW_INTERVAL_SECS, MEND_INTERVAL_SECS, PUSH_DISTANCE_PX,
+        PUSH_INTERVAL_SECS,
+    },
+    boss::Boss,
+    garden::{Garden, Plant, GARDEN_MAX_HP, PLANT_MAX_HP},
+    interaction::Interactable,
+    scenario::Scenario,
+    slime::{RandomMovement, Slime, SlimeColor},
+    spider::{Spider, SpiderHealth, Webbed},
+    GARDEN_X,
+};
+
+mod common;
+
+use common::{slime, spider};
+
+fn updates(seconds: f32) -> usize {
+    (seconds * 60.).ceil() as usize + 1
+}
+
+/// Slimes that won't wander off in the middle of a test.
+fn hold_still(scenario: &mut Scenario, entity: Entity) {
+    scenario
+        .app
+        .world
+        .entity_mut(entity)
+        .remove::<RandomMovement>();
+}
+
+#[test]
+fn every_color_has_its_own_ability_and_circle() {
+    let mut abilities: Vec<SlimeAbility> = Vec::new();
+    for color in SlimeColor::ALL {
+        let ability = SlimeAbility::of(color);
+        assert!(
+            !abilities.contains(&ability),
+            "{color:?} shares {ability:?}"
+        );
+        abilities.push(ability);
+    }
+
+    let mut scenario = Scenario::empty(0);
+    let entity = scenario.spawn_slime(slime(SlimeColor::Yellow, 2), Vec2::ZERO);
+    scenario.step();
+    let world = &mut scenario.app.world;
+    let children = world.get::<Children>(entity).unwrap().to_vec();
+    assert!(children
+        .iter()
+        .any(|&child| world.get::<AbilityCircle>(child).is_some()));
+    assert!(
+        SlimeAbility::Slow.radius(&slime(SlimeColor::Yellow, 2))
+            > SlimeAbility::Slow.radius(&slime(SlimeColor::Yellow, 1))
+    );
+}
+
+#[test]
+fn yellow_slimes_slow_spiders_down() {
+    let mut plain = Scenario::empty(0);
+    let walker = Spider {
+        speed: 60.,
+        ..spider(3, SlimeColor::Red)
+    };
+    let free = plain.spawn_spider(walker, Vec2::new(0., 0.));
+    plain.step_n(30);
+
+    let mut slowed = Scenario::empty(0);
+    let yellow = slowed.spawn_slime(slime(SlimeColor::Yellow, 5), Vec2::new(0., 0.));
+    hold_still(&mut slowed, yellow);
+    let held = slowed.spawn_spider(walker, Vec2::new(0., 0.));
+    slowed.step_n(30);
+
+    let free_distance = -plain.position(free).x;
+    let held_distance = -slowed.position(held).x;
+    assert!(held_distance < free_distance * 0.75);
+}
+
+#[test]
+fn blue_slimes_push_spiders_back() {
+    let mut scenario = Scenario::empty(0);
+    let blue = scenario.spawn_slime(slime(SlimeColor::Blue, 3), Vec2::new(0., 100.));
+    hold_still(&mut scenario, blue);
+    let target = scenario.spawn_spider(spider(1, SlimeColor::Red), Vec2::new(0., 0.));
+    scenario.step_n(updates(PUSH_INTERVAL_SECS));
+    assert!((scenario.position(target).x - PUSH_DISTANCE_PX).abs() < 1.);
+}
+
+#[test]
+fn red_slimes_burn_spiders_but_never_finish_them() {
+    let mut scenario = Scenario::empty(0);
+    let red = scenario.spawn_slime(slime(SlimeColor::Red, 3), Vec2::new(0., 100.));
+    hold_still(&mut scenario, red);
+    let target = scenario.spawn_spider(spider(1, SlimeColor::Red), Vec2::new(0., 0.));
+    scenario.step_n(updates(BURN_INTERVAL_SECS));
+    let health = *scenario.app.world.get::<SpiderHealth>(target).unwrap();
+    assert_eq!(health.hp, health.max_hp - 1);
+    scenario.step_n(updates(BURN_INTERVAL_SECS * 10.));
+    assert_eq!(
+        scenario.app.world.get::<SpiderHealth>(target).unwrap().hp,
+        1
+    );
+}
+
+#[test]
+fn red_slimes_only_burn_bosses_in_a_red_phase() {
+    let mut burned_hp = Vec::new();
+    for weaknesses in [
+        vec![SlimeColor::Blue, SlimeColor::Red],
+        vec![SlimeColor::Red, SlimeColor::Blue],
+    ] {
+        let mut scenario = Scenario::empty(0);
+        let red = scenario.spawn_slime(slime(SlimeColor::Red, 3), Vec2::new(0., 100.));
+        hold_still(&mut scenario, red);
+        let boss = Boss {
+            weaknesses,
+            phase: 0,
+            hp_per_phase: 6,
+            minions: 0,
+            minion_level: 1,
+        };
+        let spider = boss.spider(1, 0.);
+        let target = scenario.spawn_boss(boss, spider, Vec2::new(0., 0.));
+        scenario.step_n(updates(BURN_INTERVAL_SECS));
+        let health = *scenario.app.world.get::<SpiderHealth>(target).unwrap();
+        burned_hp.push(health.max_hp - health.hp);
+    }
+    assert_eq!(burned_hp, [0, 1]);
+}
+
+#[test]
+fn cyan_slimes_freeze_spiders_for_a_while() {
+    let mut scenario = Scenario::empty(0);
+    let cyan = scenario.spawn_slime(slime(SlimeColor::Cyan, 5), Vec2::new(0., 0.));
+    hold_still(&mut scenario, cyan);
+    let target = scenario.spawn_spider(spider(3, SlimeColor::Red), Vec2::new(AURA_RADIUS_PX, 0.));
+    scenario.step_n(updates(FREEZE_INTERVAL_SECS));
+    assert!(scenario.app.world.get::<Frozen>(target).is_some());
+    let position = scenario.position(target);
+    scenario.app.world.get_mut::<Spider>(target).unwrap().speed = 60.;
+    scenario.step_n(30);
+    assert_eq!(scenario.position(target), position);
+}
+
+#[test]
+fn green_slimes_mend_the_garden_they_are_near() {
+    let mut scenario = Scenario::empty(0);
+    let world = &mut scenario.app.world;
+    let mut plants = world.query::<&mut Plant>();
+    let mut plants = plants.iter_mut(world);
+    // the most eaten plant gets mended, and the eaten one stays eaten.
+    plants.next().unwrap().hp = 0;
+    plants.next().unwrap().hp = 1;
+    plants.next().unwrap().hp = 2;
+    let near = scenario.spawn_slime(slime(SlimeColor::Green, 1), Vec2::new(GARDEN_X, 0.));
+    let far = scenario.spawn_slime(slime(SlimeColor::Green, 1), Vec2::new(400., 0.));
+    hold_still(&mut scenario, near);
+    hold_still(&mut scenario, far);
+    scenario.step_n(updates(MEND_INTERVAL_SECS));
+    assert_eq!(
+        scenario.app.world.resource::<Garden>().hp,
+        GARDEN_MAX_HP - 3 * PLANT_MAX_HP + 2 + 2
+    );
+}
+
+#[test]
+fn purple_slimes_keep_webs_off_their_neighbours() {
+    let mut scenario = Scenario::empty(0);
+    let purple = scenario.spawn_slime(slime(SlimeColor::Purple, 1), Vec2::new(0., 0.));
+    let neighbour = scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::new(50., 0.));
+    hold_still(&mut scenario, purple);
+    scenario
+        .app
+        .world
+        .entity_mut(neighbour)
+        .insert(Webbed(Timer::from_seconds(10., false)));
+    scenario.step();
+    assert!(scenario.app.world.get::<Webbed>(neighbour).is_none());
+}
+
+#[test]
+fn white_slimes_grow_over_time() {
+    let mut scenario = Scenario::empty(0);
+    scenario.spawn_slime(slime(SlimeColor::White, 1), Vec2::new(0., 0.));
+    scenario.step_n(updates(GROW_INTERVAL_SECS));
+    assert_eq!(scenario.slimes(), vec![slime(SlimeColor::White, 2)]);
+}
+
+#[test]
+fn growing_slimes_stay_the_same_slime() {
+    let mut scenario = Scenario::empty(0);
+    let grower = scenario.spawn_slime(slime(SlimeColor::White, 1), Vec2::new(0., 0.));
+    scenario
+        .app
+        .world
+        .entity_mut(grower)
+        .insert(Webbed(Timer::from_seconds(60., false)));
+    scenario.step_n(updates(GROW_INTERVAL_SECS));
+    let world = &scenario.app.world;
+    assert_eq!(
+        *world.get::<Slime>(grower).unwrap(),
+        slime(SlimeColor::White, 2)
+    );
+    assert!(world.get::<Webbed>(grower).is_some());
+    assert_eq!(
+        world.get::<Interactable>(grower).unwrap().activation_radius,
+        slime(SlimeColor::White, 2).radius_px()
+    );
+}
@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    boss::{attack_boss, Boss, BossHealthBar},
+    combine::WEAKNESS_DAMAGE_MULTIPLIER,
+    scenario::Scenario,
+    slime::SlimeColor,
+    spider::{Spider, SpiderHealth},
+    waves::WaveConfig,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+mod common;
+
+use common::slime;
+
+fn boss() -> Boss {
+    Boss {
+        weaknesses: vec![SlimeColor::Red, SlimeColor::Blue, SlimeColor::Green],
+        phase: 0,
+        hp_per_phase: 6,
+        minions: 2,
+        minion_level: 1,
+    }
+}
+
+/// A boss that stays where it is put.
+fn boss_spider(boss: &Boss) -> Spider {
+    boss.spider(4, 0.)
+}
+
+#[test]
+fn only_the_current_weakness_hurts_a_boss() {
+    let boss = boss();
+    let spider = boss_spider(&boss);
+    let health = boss.phase_health();
+    let wrong = attack_boss(&spider, &health, &boss, &slime(SlimeColor::Blue, 5));
+    assert_eq!(wrong.spider_damage, 0);
+    assert!(!wrong.spider_killed);
+    let right = attack_boss(&spider, &health, &boss, &slime(SlimeColor::Red, 2));
+    assert_eq!(right.spider_damage, 2 * WEAKNESS_DAMAGE_MULTIPLIER);
+    assert!(right.brood.is_empty());
+}
+
+#[test]
+fn clearing_a_phase_summons_minions_of_the_next_weakness() {
+    let boss = boss();
+    let spider = boss_spider(&boss);
+    let outcome = attack_boss(
+        &spider,
+        &boss.phase_health(),
+        &boss,
+        &slime(SlimeColor::Red, 3),
+    );
+    assert!(!outcome.spider_killed);
+    assert_eq!(outcome.score_delta, 0);
+    assert_eq!(outcome.brood.len(), 2);
+    assert!(outcome
+        .brood
+        .iter()
+        .all(|minion| minion.level == 1 && minion.weakness == SlimeColor::Blue));
+}
+
+#[test]
+fn clearing_the_last_phase_kills_the_boss() {
+    let mut boss = boss();
+    let mut spider = boss_spider(&boss);
+    let mut health = boss.phase_health();
+    boss.next_phase(&mut spider, &mut health);
+    boss.next_phase(&mut spider, &mut health);
+    assert!(boss.is_last_phase());
+    assert_eq!(spider.weakness, SlimeColor::Green);
+    let outcome = attack_boss(&spider, &health, &boss, &slime(SlimeColor::Green, 3));
+    assert!(outcome.spider_killed);
+    assert_eq!(outcome.score_delta, 1);
+    assert!(outcome.brood.is_empty());
+}
+
+#[test]
+fn health_fraction_counts_the_phases_to_come() {
+    let mut boss = boss();
+    let mut spider = boss_spider(&boss);
+    let mut health = boss.phase_health();
+    assert_eq!(boss.fraction(&health), 1.);
+    boss.next_phase(&mut spider, &mut health);
+    health.hp = 3;
+    assert_eq!(boss.fraction(&health), 0.5);
+}
+
+#[test]
+fn drawn_bosses_never_repeat_a_weakness_back_to_back() {
+    let waves = WaveConfig::default();
+    let wave = waves.boss(40).unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let boss = Boss::from_wave(wave, &mut rng);
+        assert_eq!(boss.weaknesses.len(), wave.phases);
+        assert!(boss.weaknesses.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+    assert!(waves.boss(39).is_none());
+}
+
+#[test]
+fn zero_phase_bosses_are_rejected() {
+    let result = WaveConfig::from_ron(
+        "(
+            levels: [(from_spawned: 0, min: 1, max: 1)],
+            interval: (base: 1.0, per_spawn: 0.0),
+            speed: (base: 1.0, per_spawn: 0.0),
+            bosses: [(at_spawned: 3, level: 5, speed: 10.0, phases: 0, hp_per_phase: 4,
+                      minions: 1, minion_level: 1)],
+        )",
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn a_boss_fights_through_its_phases_with_a_health_bar() {
+    let mut scenario = Scenario::empty(0);
+    let boss = boss();
+    let target = scenario.spawn_boss(boss.clone(), boss_spider(&boss), Vec2::new(300., 0.));
+    scenario.step();
+    let bars = |scenario: &mut Scenario| {
+        scenario
+            .app
+            .world
+            .query::<&BossHealthBar>()
+            .iter(&scenario.app.world)
+            .count()
+    };
+    assert_eq!(bars(&mut scenario), 1);
+
+    for (phase, color) in boss.weaknesses.iter().enumerate() {
+        let attacker = scenario.spawn_slime(slime(*color, 3), Vec2::new(-200., 0.));
+        scenario.drag_onto(attacker, target).step();
+        if phase + 1 < boss.weaknesses.len() {
+            let world = &scenario.app.world;
+            assert_eq!(world.get::<Boss>(target).unwrap().phase, phase + 1);
+            assert_eq!(
+                world.get::<Spider>(target).unwrap().weakness,
+                boss.weaknesses[phase + 1]
+            );
+            assert_eq!(
+                *world.get::<SpiderHealth>(target).unwrap(),
+                boss.phase_health()
+            );
+        }
+    }
+    assert!(!scenario.exists(target));
+    assert_eq!(scenario.score().spiders_killed, 1);
+    // two rounds of minions.
+    assert_eq!(scenario.spiders().len(), 4);
+    scenario.step();
+    assert_eq!(bars(&mut scenario), 0);
+}
@@ -0,0 +1,414 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    combine::{
+        attack_spider, merge_slimes, ColorMix, ColorMixing, CombineOutcome, OverflowPolicy,
+        SLIME_MEGA_SIZE_MAX, WEAKNESS_DAMAGE_MULTIPLIER,
+    },
+    interaction::CombineEvent,
+    scenario::Scenario,
+    slime::{Slime, SlimeColor, SLIME_SIZE_MAX},
+    spider::{Spider, SpiderHealth, SpiderKind, ARMORED_MIN_SLIME_SIZE, BROOD_SIZE},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+mod common;
+
+use common::{slime, spider};
+
+fn rng() -> StdRng {
+    StdRng::seed_from_u64(0)
+}
+
+#[test]
+fn merge_sums_sizes_and_mixes_the_colors() {
+    let outcome = merge_slimes(
+        &slime(SlimeColor::Red, 1),
+        &slime(SlimeColor::Blue, 2),
+        &ColorMixing::default(),
+        OverflowPolicy::Shatter,
+        &mut rng(),
+    );
+    assert_eq!(
+        outcome,
+        CombineOutcome {
+            spawned: vec![slime(SlimeColor::Purple, 3)],
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn merge_up_to_the_max_size_does_not_split() {
+    let outcome = merge_slimes(
+        &slime(SlimeColor::Red, 2),
+        &slime(SlimeColor::Red, 3),
+        &ColorMixing::default(),
+        OverflowPolicy::Shatter,
+        &mut rng(),
+    );
+    assert_eq!(
+        outcome.spawned,
+        vec![slime(SlimeColor::Red, SLIME_SIZE_MAX)]
+    );
+}
+
+#[test]
+fn merge_over_the_max_size_splits_into_four() {
+    let outcome = merge_slimes(
+        &slime(SlimeColor::Red, 3),
+        &slime(SlimeColor::Green, 4),
+        &ColorMixing::default(),
+        OverflowPolicy::Shatter,
+        &mut rng(),
+    );
+    assert_eq!(outcome.spawned.len(), 4);
+    assert_eq!(
+        outcome.spawned[..3],
+        [
+            slime(SlimeColor::Yellow, 2),
+            slime(SlimeColor::Yellow, 3),
+            slime(SlimeColor::Yellow, 2),
+        ]
+    );
+    assert_eq!(outcome.spawned[3].size, 1);
+    assert!(!outcome.spider_killed);
+    assert_eq!(outcome.score_delta, 0);
+}
+
+#[test]
+fn merge_overflow_is_capped_at_the_max_size() {
+    let outcome = merge_slimes(
+        &slime(SlimeColor::Red, 5),
+        &slime(SlimeColor::Red, 5),
+        &ColorMixing::default(),
+        OverflowPolicy::Shatter,
+        &mut rng(),
+    );
+    assert_eq!(outcome.spawned[2], slime(SlimeColor::Red, SLIME_SIZE_MAX));
+}
+
+#[test]
+fn merge_overflow_color_comes_from_the_rng() {
+    let base = slime(SlimeColor::Red, 5);
+    let addition = slime(SlimeColor::Red, 5);
+    let mixing = ColorMixing::default();
+    let merge = || {
+        merge_slimes(
+            &base,
+            &addition,
+            &mixing,
+            OverflowPolicy::Shatter,
+            &mut StdRng::seed_from_u64(1),
+        )
+    };
+    let (first, second) = (merge(), merge());
+    assert_eq!(first, second);
+}
+
+/// Merges a slime of `size - SLIME_SIZE_MAX` into one of the max size under `policy`.
+fn overflow(policy: OverflowPolicy, size: u32) -> Vec<Slime> {
+    merge_slimes(
+        &slime(SlimeColor::Red, SLIME_SIZE_MAX),
+        &slime(SlimeColor::Red, size - SLIME_SIZE_MAX),
+        &ColorMixing::default(),
+        policy,
+        &mut rng(),
+    )
+    .spawned
+}
+
+fn total_size(slimes: &[Slime]) -> u32 {
+    slimes.iter().map(|slime| slime.size).sum()
+}
+
+#[test]
+fn split_and_mega_overflow_conserve_the_total_size() {
+    for policy in [OverflowPolicy::Split, OverflowPolicy::Mega] {
+        for size in SLIME_SIZE_MAX + 1..=2 * SLIME_MEGA_SIZE_MAX {
+            let spawned = overflow(policy, size);
+            assert_eq!(total_size(&spawned), size, "{policy:?} at {size}");
+            assert!(spawned
+                .iter()
+                .all(|slime| slime.color == SlimeColor::Red && slime.size >= 1));
+        }
+    }
+}
+
+#[test]
+fn capped_overflow_keeps_only_the_max_size() {
+    for size in SLIME_SIZE_MAX + 1..=2 * SLIME_SIZE_MAX {
+        assert_eq!(
+            overflow(OverflowPolicy::Cap, size),
+            vec![slime(SlimeColor::Red, SLIME_SIZE_MAX)]
+        );
+    }
+}
+
+#[test]
+fn shattered_overflow_adds_a_random_slime_on_top() {
+    for size in SLIME_SIZE_MAX + 1..=2 * SLIME_SIZE_MAX {
+        let spawned = overflow(OverflowPolicy::Shatter, size);
+        assert_eq!(total_size(&spawned), size + 1, "at {size}");
+    }
+}
+
+#[test]
+fn split_overflow_stays_under_the_max_and_evens_out() {
+    let spawned = overflow(OverflowPolicy::Split, 11);
+    assert_eq!(
+        spawned.iter().map(|slime| slime.size).collect::<Vec<_>>(),
+        [4, 4, 3]
+    );
+}
+
+#[test]
+fn mega_overflow_grows_past_the_max_size() {
+    assert_eq!(
+        overflow(OverflowPolicy::Mega, 8),
+        vec![slime(SlimeColor::Red, 8)]
+    );
+    let spawned = overflow(OverflowPolicy::Mega, SLIME_MEGA_SIZE_MAX + 3);
+    assert_eq!(spawned.len(), 2);
+    assert!(spawned
+        .iter()
+        .all(|slime| slime.size <= SLIME_MEGA_SIZE_MAX));
+}
+
+#[test]
+fn the_game_merges_with_the_policy_it_was_given() {
+    let mut scenario = Scenario::empty(0);
+    scenario.app.insert_resource(OverflowPolicy::Mega);
+    let base = scenario.spawn_slime(slime(SlimeColor::Red, 4), Vec2::new(-200., 0.));
+    let addition = scenario.spawn_slime(slime(SlimeColor::Red, 5), Vec2::new(200., 0.));
+    scenario.drag_onto(addition, base).step();
+    assert_eq!(scenario.slimes(), vec![slime(SlimeColor::Red, 9)]);
+}
+
+#[test]
+fn mixing_works_in_either_order() {
+    let mixing = ColorMixing::default();
+    assert_eq!(
+        mixing.mix(SlimeColor::Red, SlimeColor::Blue),
+        SlimeColor::Purple
+    );
+    assert_eq!(
+        mixing.mix(SlimeColor::Blue, SlimeColor::Red),
+        SlimeColor::Purple
+    );
+    assert_eq!(
+        mixing.mix(SlimeColor::Yellow, SlimeColor::Blue),
+        SlimeColor::Green
+    );
+}
+
+#[test]
+fn white_and_black_undo_each_other() {
+    let mixing = ColorMixing::default();
+    for color in SlimeColor::ALL {
+        let darker = mixing.mix(color, SlimeColor::Black);
+        if darker != SlimeColor::Black {
+            assert_eq!(mixing.mix(darker, SlimeColor::White), color);
+        }
+    }
+}
+
+#[test]
+fn same_colors_and_unlisted_pairs_keep_the_addition_color() {
+    let mixing = ColorMixing::default();
+    for color in SlimeColor::ALL {
+        assert_eq!(mixing.mix(color, color), color);
+    }
+    assert_eq!(
+        mixing.mix(SlimeColor::Cyan, SlimeColor::Purple),
+        SlimeColor::Purple
+    );
+}
+
+#[test]
+fn mixing_table_is_configurable() {
+    let mixing =
+        ColorMixing::from_ron("(mixes: [(colors: (Red, Yellow), result: White)])").unwrap();
+    assert_eq!(
+        mixing.mixes,
+        vec![ColorMix {
+            colors: (SlimeColor::Red, SlimeColor::Yellow),
+            result: SlimeColor::White,
+        }]
+    );
+    let outcome = merge_slimes(
+        &slime(SlimeColor::Yellow, 1),
+        &slime(SlimeColor::Red, 1),
+        &mixing,
+        OverflowPolicy::Shatter,
+        &mut rng(),
+    );
+    assert_eq!(outcome.spawned, vec![slime(SlimeColor::White, 2)]);
+    assert_eq!(
+        mixing.mix(SlimeColor::Red, SlimeColor::Blue),
+        SlimeColor::Blue
+    );
+}
+
+/// Attacks a spider at full health.
+fn attack(spider: &Spider, slime: &Slime) -> CombineOutcome {
+    attack_spider(spider, &SpiderHealth::full(spider.level), slime)
+}
+
+#[test]
+fn attack_with_matching_color_and_enough_size_kills() {
+    let outcome = attack(&spider(3, SlimeColor::Blue), &slime(SlimeColor::Blue, 3));
+    assert_eq!(
+        outcome,
+        CombineOutcome {
+            spawned: vec![slime(SlimeColor::Blue, 1), slime(SlimeColor::Blue, 2)],
+            brood: vec![],
+            spider_damage: 3 * WEAKNESS_DAMAGE_MULTIPLIER,
+            spider_killed: true,
+            score_delta: 1,
+        }
+    );
+}
+
+#[test]
+fn attack_bigger_than_the_spider_kills() {
+    let outcome = attack(&spider(2, SlimeColor::Blue), &slime(SlimeColor::Blue, 5));
+    assert!(outcome.spider_killed);
+    assert_eq!(
+        outcome.spawned,
+        vec![slime(SlimeColor::Blue, 2), slime(SlimeColor::Blue, 3)]
+    );
+}
+
+#[test]
+fn attack_with_the_wrong_color_deals_its_size() {
+    let outcome = attack(&spider(4, SlimeColor::Blue), &slime(SlimeColor::Red, 5));
+    assert_eq!(outcome.spider_damage, 5);
+    assert!(!outcome.spider_killed);
+    assert_eq!(outcome.score_delta, 0);
+    assert_eq!(
+        outcome.spawned,
+        vec![slime(SlimeColor::Red, 2), slime(SlimeColor::Red, 3)]
+    );
+}
+
+#[test]
+fn attack_too_small_only_hurts() {
+    let outcome = attack(&spider(4, SlimeColor::Blue), &slime(SlimeColor::Blue, 3));
+    assert_eq!(outcome.spider_damage, 6);
+    assert!(!outcome.spider_killed);
+    assert_eq!(outcome.score_delta, 0);
+}
+
+#[test]
+fn attack_finishes_off_a_worn_down_spider() {
+    let health = SpiderHealth { hp: 1, max_hp: 10 };
+    let outcome = attack_spider(
+        &spider(5, SlimeColor::Blue),
+        &health,
+        &slime(SlimeColor::Red, 1),
+    );
+    assert!(outcome.spider_killed);
+    assert_eq!(outcome.score_delta, 1);
+}
+
+#[test]
+fn attack_with_a_size_one_slime_leaves_one_slime() {
+    let outcome = attack(&spider(1, SlimeColor::Blue), &slime(SlimeColor::Blue, 1));
+    assert!(outcome.spider_killed);
+    assert_eq!(outcome.spawned, vec![slime(SlimeColor::Blue, 1)]);
+}
+
+#[test]
+fn armored_spiders_shrug_off_small_slimes() {
+    let armored = Spider {
+        kind: SpiderKind::Armored,
+        ..spider(2, SlimeColor::Blue)
+    };
+    let small = attack(
+        &armored,
+        &slime(SlimeColor::Blue, ARMORED_MIN_SLIME_SIZE - 1),
+    );
+    assert_eq!(small.spider_damage, 0);
+    assert!(!small.spider_killed);
+    let large = attack(&armored, &slime(SlimeColor::Blue, ARMORED_MIN_SLIME_SIZE));
+    assert!(large.spider_killed);
+}
+
+#[test]
+fn brood_mothers_hatch_when_they_die() {
+    let mother = Spider {
+        kind: SpiderKind::BroodMother,
+        ..spider(3, SlimeColor::Blue)
+    };
+    let outcome = attack(&mother, &slime(SlimeColor::Blue, 3));
+    assert!(outcome.spider_killed);
+    assert_eq!(
+        outcome.brood,
+        vec![
+            Spider {
+                kind: SpiderKind::Common,
+                ..spider(2, SlimeColor::Blue)
+            };
+            BROOD_SIZE
+        ]
+    );
+    let hurt = attack(&mother, &slime(SlimeColor::Red, 1));
+    assert!(hurt.brood.is_empty());
+}
+
+#[test]
+fn level_one_brood_mothers_hatch_nothing() {
+    let mother = Spider {
+        kind: SpiderKind::BroodMother,
+        ..spider(1, SlimeColor::Blue)
+    };
+    assert!(attack(&mother, &slime(SlimeColor::Blue, 1))
+        .brood
+        .is_empty());
+}
+
+#[test]
+fn black_slimes_come_back_whole_from_a_failed_attack() {
+    let armored = Spider {
+        kind: SpiderKind::Armored,
+        ..spider(4, SlimeColor::Blue)
+    };
+    let small = slime(SlimeColor::Black, ARMORED_MIN_SLIME_SIZE - 1);
+    let failed = attack(&armored, &small);
+    assert_eq!(failed.spider_damage, 0);
+    assert_eq!(failed.spawned, vec![small]);
+    // hurting the spider counts as an attack like any other, even if it survives.
+    let hurt = attack(&spider(4, SlimeColor::Blue), &slime(SlimeColor::Black, 3));
+    assert!(!hurt.spider_killed);
+    assert_eq!(
+        hurt.spawned,
+        vec![slime(SlimeColor::Black, 1), slime(SlimeColor::Black, 2)]
+    );
+    let killed = attack(&spider(1, SlimeColor::Blue), &slime(SlimeColor::Black, 3));
+    assert!(killed.spider_killed);
+    assert_eq!(
+        killed.spawned,
+        vec![slime(SlimeColor::Black, 1), slime(SlimeColor::Black, 2)]
+    );
+}
+
+#[test]
+fn a_spider_is_only_killed_once_however_many_slimes_hit_it_at_once() {
+    let mut scenario = Scenario::empty(0);
+    let target = scenario.spawn_spider(spider(2, SlimeColor::Red), Vec2::new(200., 0.));
+    let first = scenario.spawn_slime(slime(SlimeColor::Red, 2), Vec2::new(-200., 100.));
+    let second = scenario.spawn_slime(slime(SlimeColor::Red, 2), Vec2::new(-200., -100.));
+    for addition in [first, second, first] {
+        scenario.app.world.send_event(CombineEvent {
+            location: Vec2::new(200., 0.),
+            base: target,
+            addition,
+        });
+    }
+    scenario.step();
+    assert_eq!(scenario.score().spiders_killed, 1);
+    assert!(!scenario.exists(target));
+    // the second slime wasn't spent on a spider that was already dead.
+    assert!(scenario.exists(second));
+    assert_eq!(scenario.slimes().len(), 3);
+}
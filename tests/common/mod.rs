@@ -0,0 +1,23 @@
+//! Fixtures shared by the integration tests. Each test crate only uses some of them.
+#![allow(dead_code)]
+
+use bevy_jam_2::{
+    pathing::SpiderMovement,
+    slime::{Slime, SlimeColor},
+    spider::{Spider, SpiderKind},
+};
+
+pub fn slime(color: SlimeColor, size: u32) -> Slime {
+    Slime { color, size }
+}
+
+/// A common spider that stays where it is put.
+pub fn spider(level: u32, weakness: SlimeColor) -> Spider {
+    Spider {
+        level,
+        weakness,
+        speed: 0.,
+        kind: SpiderKind::Common,
+        movement: SpiderMovement::Straight,
+    }
+}
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    defender::{Defender, DefenderConfig},
+    interaction::ActivationCircle,
+    scenario::Scenario,
+    slime::{RandomMovement, Slime, SlimeColor},
+};
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+
+mod common;
+
+use common::{slime, spider};
+
+fn scenario(enabled: bool) -> Scenario {
+    let mut scenario = Scenario::empty(0);
+    scenario.app.insert_resource(DefenderConfig {
+        enabled,
+        ..default()
+    });
+    scenario
+}
+
+/// Spawns a slime that won't wander off on its own.
+fn spawn_slime(scenario: &mut Scenario, slime: Slime, position: Vec2) -> Entity {
+    let entity = scenario.spawn_slime(slime, position);
+    scenario
+        .app
+        .world
+        .entity_mut(entity)
+        .remove::<RandomMovement>();
+    entity
+}
+
+fn seconds(config: &DefenderConfig) -> usize {
+    ((config.cooldown_secs + config.windup_secs) * 60.) as usize + 60
+}
+
+#[test]
+fn defenders_are_off_unless_asked_for() {
+    let mut scenario = scenario(false);
+    spawn_slime(&mut scenario, slime(SlimeColor::Red, 3), Vec2::new(0., 0.));
+    let target = scenario.spawn_spider(spider(1, SlimeColor::Red), Vec2::new(200., 0.));
+    scenario.step_n(seconds(&DefenderConfig::default()));
+    assert!(scenario.exists(target));
+}
+
+#[test]
+fn a_big_slime_goes_after_a_spider_of_its_color() {
+    let mut scenario = scenario(true);
+    let defender = spawn_slime(&mut scenario, slime(SlimeColor::Red, 3), Vec2::new(0., 0.));
+    let target = scenario.spawn_spider(spider(1, SlimeColor::Red), Vec2::new(200., 0.));
+    scenario.step_n(seconds(&DefenderConfig::default()));
+    assert!(!scenario.exists(defender));
+    assert!(!scenario.exists(target));
+    assert_eq!(scenario.score().spiders_killed, 1);
+}
+
+#[test]
+fn small_or_mismatched_slimes_leave_spiders_alone() {
+    let mut scenario = scenario(true);
+    spawn_slime(
+        &mut scenario,
+        slime(SlimeColor::Red, 2),
+        Vec2::new(0., 100.),
+    );
+    spawn_slime(
+        &mut scenario,
+        slime(SlimeColor::Blue, 5),
+        Vec2::new(0., -100.),
+    );
+    let target = scenario.spawn_spider(spider(1, SlimeColor::Red), Vec2::new(200., 0.));
+    scenario.step_n(seconds(&DefenderConfig::default()));
+    assert!(scenario.exists(target));
+}
+
+#[test]
+fn defenders_outline_themselves_while_winding_up() {
+    let mut scenario = scenario(true);
+    let defender = spawn_slime(&mut scenario, slime(SlimeColor::Red, 3), Vec2::new(0., 0.));
+    scenario.spawn_spider(spider(1, SlimeColor::Red), Vec2::new(240., 0.));
+    let cooldown = DefenderConfig::default().cooldown_secs;
+    scenario.step_n((cooldown * 60.) as usize + 2);
+    let world = &mut scenario.app.world;
+    assert!(world.get::<Defender>(defender).unwrap().engaging.is_some());
+    let outline = world
+        .get::<Children>(defender)
+        .unwrap()
+        .iter()
+        .find_map(|&child| {
+            world.get::<ActivationCircle>(child)?;
+            match world.get::<DrawMode>(child)? {
+                DrawMode::Outlined { outline_mode, .. } => Some(outline_mode.color),
+                _ => None,
+            }
+        });
+    assert_eq!(outline, Some(SlimeColor::Red.color()));
+}
+
+#[test]
+fn defenders_stop_when_a_spider_gets_away() {
+    let mut scenario = scenario(true);
+    let config = DefenderConfig::default();
+    let defender = spawn_slime(&mut scenario, slime(SlimeColor::Red, 3), Vec2::new(0., 0.));
+    let target = scenario.spawn_spider(spider(1, SlimeColor::Red), Vec2::new(240., 0.));
+    scenario.step_n((config.cooldown_secs * 60.) as usize + 10);
+    assert!(
+        scenario
+            .app
+            .world
+            .get::<Velocity>(defender)
+            .unwrap()
+            .linvel
+            .length()
+            > 0.
+    );
+    scenario
+        .app
+        .world
+        .get_mut::<Transform>(target)
+        .unwrap()
+        .translation
+        .y = config.range_px * 2.;
+    scenario.step_n(2);
+    let world = &scenario.app.world;
+    assert!(world.get::<Defender>(defender).unwrap().engaging.is_none());
+    assert_eq!(world.get::<Velocity>(defender).unwrap().linvel, Vec2::ZERO);
+}
@@ -0,0 +1,191 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    interaction::DragActive,
+    scenario::Scenario,
+    slime::{Slime, SlimeColor},
+    spider::{Spider, SpiderHealth, SpiderKind, Webbed, WEB_DURATION_SECS, WEB_INTERVAL_SECS},
+    DRAG_LAYER, MAIN_LAYER,
+};
+use bevy_rapier2d::prelude::*;
+
+mod common;
+
+use common::{slime, spider};
+
+fn sorted(mut slimes: Vec<Slime>) -> Vec<Slime> {
+    slimes.sort_by_key(|slime| (slime.size, slime.color as u8));
+    slimes
+}
+
+#[test]
+fn pressing_on_a_slime_picks_it_up() {
+    let mut scenario = Scenario::empty(0);
+    let entity = scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::new(0., 0.));
+    let position = scenario.position(entity);
+    scenario.press(position);
+    let world = &scenario.app.world;
+    assert!(world.get::<DragActive>(entity).unwrap().0);
+    assert_eq!(world.get::<CollisionGroups>(entity).unwrap().filters, 0);
+    assert_eq!(
+        world.get::<Transform>(entity).unwrap().translation.z,
+        DRAG_LAYER
+    );
+}
+
+#[test]
+fn pressing_away_from_slimes_picks_nothing_up() {
+    let mut scenario = Scenario::empty(0);
+    let entity = scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::new(0., 0.));
+    scenario.press(Vec2::new(300., 200.));
+    assert!(!scenario.app.world.get::<DragActive>(entity).unwrap().0);
+}
+
+#[test]
+fn a_dragged_slime_follows_the_mouse() {
+    let mut scenario = Scenario::empty(0);
+    let entity = scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::new(0., 0.));
+    let position = scenario.position(entity);
+    scenario.press(position);
+    scenario.move_mouse(Some(Vec2::new(120., -80.))).step();
+    assert_eq!(scenario.position(entity), Vec2::new(120., -80.));
+}
+
+#[test]
+fn dropping_on_empty_ground_puts_the_slime_down() {
+    let mut scenario = Scenario::empty(0);
+    let entity = scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::new(0., 0.));
+    let position = scenario.position(entity);
+    scenario.drag(position, Vec2::new(200., 100.));
+    let world = &scenario.app.world;
+    assert!(!world.get::<DragActive>(entity).unwrap().0);
+    assert_eq!(world.get::<CollisionGroups>(entity).unwrap().filters, !0);
+    assert_eq!(
+        world.get::<Transform>(entity).unwrap().translation.z,
+        MAIN_LAYER
+    );
+    assert_eq!(scenario.position(entity), Vec2::new(200., 100.));
+    assert_eq!(scenario.slimes(), vec![slime(SlimeColor::Red, 1)]);
+}
+
+#[test]
+fn dropping_a_slime_on_another_merges_them() {
+    let mut scenario = Scenario::empty(0);
+    let base = scenario.spawn_slime(slime(SlimeColor::Red, 2), Vec2::new(-200., 0.));
+    let addition = scenario.spawn_slime(slime(SlimeColor::Blue, 1), Vec2::new(200., 0.));
+    scenario.drag_onto(addition, base);
+    assert!(!scenario.exists(base));
+    assert!(!scenario.exists(addition));
+    assert_eq!(scenario.slimes(), vec![slime(SlimeColor::Purple, 3)]);
+    assert_eq!(scenario.score().spiders_killed, 0);
+}
+
+#[test]
+fn merging_past_the_max_size_splits() {
+    let mut scenario = Scenario::empty(0);
+    let base = scenario.spawn_slime(slime(SlimeColor::Red, 4), Vec2::new(-200., 0.));
+    let addition = scenario.spawn_slime(slime(SlimeColor::Green, 3), Vec2::new(200., 0.));
+    scenario.drag_onto(addition, base);
+    let slimes = scenario.slimes();
+    assert_eq!(slimes.len(), 4);
+    assert_eq!(
+        slimes
+            .iter()
+            .filter(|slime| slime.color == SlimeColor::Yellow && slime.size > 1)
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn a_matching_attack_kills_the_spider() {
+    let mut scenario = Scenario::empty(0);
+    let target = scenario.spawn_spider(spider(2, SlimeColor::Yellow), Vec2::new(300., 0.));
+    let attacker = scenario.spawn_slime(slime(SlimeColor::Yellow, 3), Vec2::new(-200., 0.));
+    scenario.drag_onto(attacker, target);
+    assert!(!scenario.exists(target));
+    assert!(scenario.spiders().is_empty());
+    assert_eq!(scenario.score().spiders_killed, 1);
+    assert_eq!(
+        sorted(scenario.slimes()),
+        vec![slime(SlimeColor::Yellow, 1), slime(SlimeColor::Yellow, 2)]
+    );
+}
+
+#[test]
+fn a_mismatched_attack_only_hurts_the_spider() {
+    let mut scenario = Scenario::empty(0);
+    let target = scenario.spawn_spider(spider(3, SlimeColor::Yellow), Vec2::new(300., 0.));
+    let attacker = scenario.spawn_slime(slime(SlimeColor::Red, 4), Vec2::new(-200., 0.));
+    scenario.drag_onto(attacker, target);
+    assert!(scenario.exists(target));
+    assert_eq!(
+        scenario.app.world.get::<SpiderHealth>(target).unwrap().hp,
+        2
+    );
+    assert_eq!(scenario.score().spiders_killed, 0);
+    assert_eq!(
+        sorted(scenario.slimes()),
+        vec![slime(SlimeColor::Red, 2), slime(SlimeColor::Red, 2)]
+    );
+}
+
+#[test]
+fn several_small_attacks_wear_a_spider_down() {
+    let mut scenario = Scenario::empty(0);
+    let target = scenario.spawn_spider(spider(3, SlimeColor::Yellow), Vec2::new(300., 0.));
+    for _ in 0..2 {
+        let attacker = scenario.spawn_slime(slime(SlimeColor::Yellow, 1), Vec2::new(-200., 0.));
+        scenario.drag_onto(attacker, target);
+        assert!(scenario.exists(target));
+    }
+    let attacker = scenario.spawn_slime(slime(SlimeColor::Yellow, 1), Vec2::new(-200., 0.));
+    scenario.drag_onto(attacker, target);
+    assert!(!scenario.exists(target));
+    assert_eq!(scenario.score().spiders_killed, 1);
+}
+
+#[test]
+fn a_brood_mother_hatches_where_it_dies() {
+    let mut scenario = Scenario::empty(0);
+    let mother = scenario.spawn_spider(
+        Spider {
+            kind: SpiderKind::BroodMother,
+            ..spider(3, SlimeColor::Red)
+        },
+        Vec2::new(300., 0.),
+    );
+    let attacker = scenario.spawn_slime(slime(SlimeColor::Red, 3), Vec2::new(-200., 0.));
+    scenario.drag_onto(attacker, mother).step();
+    assert!(!scenario.exists(mother));
+    assert_eq!(scenario.score().spiders_killed, 1);
+    let brood = scenario.spiders();
+    assert_eq!(brood.len(), 2);
+    assert!(brood
+        .iter()
+        .all(|spider| spider.level == 2 && spider.kind == SpiderKind::Common));
+}
+
+#[test]
+fn a_web_spitter_holds_nearby_slimes_in_place() {
+    let mut scenario = Scenario::empty(0);
+    scenario.spawn_spider(
+        Spider {
+            kind: SpiderKind::WebSpitter,
+            ..spider(2, SlimeColor::Red)
+        },
+        Vec2::new(300., 0.),
+    );
+    let near = scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::new(200., 0.));
+    let far = scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::new(-300., 0.));
+    scenario.step_n((WEB_INTERVAL_SECS * 60.) as usize);
+    assert!(scenario.app.world.get::<Webbed>(near).is_some());
+    assert!(scenario.app.world.get::<Webbed>(far).is_none());
+
+    let position = scenario.position(near);
+    scenario.press(position);
+    assert!(!scenario.app.world.get::<DragActive>(near).unwrap().0);
+    scenario.release(position);
+
+    scenario.step_n((WEB_DURATION_SECS * 60.) as usize);
+    assert!(scenario.app.world.get::<Webbed>(near).is_none());
+}
@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    garden::{
+        garden_damage, plant_position, Garden, Plant, GARDEN_DAMAGE_PER_LEVEL, GARDEN_MAX_HP,
+        PLANT_COUNT, PLANT_MAX_HP, PLANT_REGROW_HP,
+    },
+    pathing::SpiderMovement,
+    scenario::Scenario,
+    score::ScoreResource,
+    slime::SlimeColor,
+    spider::{Spider, SpiderKind},
+    waves::WaveConfig,
+    AppState,
+};
+
+/// A spider fast enough to get to the garden in a couple of seconds.
+fn runner(level: u32) -> Spider {
+    Spider {
+        level,
+        weakness: SlimeColor::Red,
+        speed: 600.,
+        kind: SpiderKind::Common,
+        movement: SpiderMovement::Straight,
+    }
+}
+
+fn garden(scenario: &Scenario) -> Garden {
+    *scenario.app.world.resource::<Garden>()
+}
+
+/// Every plant's hit points, top to bottom.
+fn plants(scenario: &mut Scenario) -> Vec<u32> {
+    let world = &mut scenario.app.world;
+    let mut plants: Vec<(f32, u32)> = world
+        .query::<(&Transform, &Plant)>()
+        .iter(world)
+        .map(|(transform, plant)| (-transform.translation.y, plant.hp))
+        .collect();
+    plants.sort_by(|a, b| a.0.total_cmp(&b.0));
+    plants.into_iter().map(|(_, hp)| hp).collect()
+}
+
+fn set_plants(scenario: &mut Scenario, hp: &[u32]) {
+    let world = &mut scenario.app.world;
+    let mut query = world.query::<(&Transform, &mut Plant)>();
+    for (transform, mut plant) in query.iter_mut(world) {
+        let index = (0..PLANT_COUNT)
+            .find(|&index| plant_position(index).y == transform.translation.y)
+            .unwrap();
+        plant.hp = hp[index];
+    }
+}
+
+#[test]
+fn runs_start_with_a_fully_grown_garden() {
+    let mut scenario = Scenario::empty(0);
+    assert_eq!(garden(&scenario).hp, GARDEN_MAX_HP);
+    assert_eq!(garden(&scenario).last_breach, None);
+    assert_eq!(plants(&mut scenario), vec![PLANT_MAX_HP; PLANT_COUNT]);
+}
+
+#[test]
+fn a_spider_eats_the_plant_nearest_to_it_and_goes_away() {
+    let mut scenario = Scenario::empty(0);
+    let spider = scenario.spawn_spider(runner(3), Vec2::new(0., plant_position(0).y - 40.));
+    scenario.step_n(120);
+    assert!(!scenario.exists(spider));
+    assert_eq!(*scenario.state(), AppState::InGame);
+    let mut expected = vec![PLANT_MAX_HP; PLANT_COUNT];
+    expected[0] -= 3 * GARDEN_DAMAGE_PER_LEVEL;
+    assert_eq!(plants(&mut scenario), expected);
+    assert_eq!(
+        garden(&scenario).hp,
+        GARDEN_MAX_HP - 3 * GARDEN_DAMAGE_PER_LEVEL
+    );
+    assert_eq!(garden(&scenario).last_breach, Some(runner(3)));
+}
+
+#[test]
+fn a_spider_still_hungry_moves_on_to_the_next_plant() {
+    let mut scenario = Scenario::empty(0);
+    let mut hp = vec![PLANT_MAX_HP; PLANT_COUNT];
+    hp[0] = 1;
+    set_plants(&mut scenario, &hp);
+    scenario.spawn_spider(runner(3), Vec2::new(0., plant_position(0).y));
+    scenario.step_n(120);
+    hp[0] = 0;
+    hp[1] -= 2;
+    assert_eq!(plants(&mut scenario), hp);
+}
+
+#[test]
+fn spiders_pass_eaten_plants_by() {
+    let mut scenario = Scenario::empty(0);
+    let mut hp = vec![PLANT_MAX_HP; PLANT_COUNT];
+    hp[2] = 0;
+    set_plants(&mut scenario, &hp);
+    scenario.spawn_spider(runner(1), Vec2::new(0., plant_position(2).y));
+    scenario.step_n(120);
+    assert_eq!(
+        garden(&scenario).hp,
+        GARDEN_MAX_HP - 2 * PLANT_MAX_HP + PLANT_MAX_HP - 1
+    );
+    assert_eq!(plants(&mut scenario)[2], 0);
+}
+
+#[test]
+fn the_run_ends_when_the_last_plant_is_eaten() {
+    let mut scenario = Scenario::empty(0);
+    let mut hp = vec![0; PLANT_COUNT];
+    hp[1] = garden_damage(&runner(2));
+    set_plants(&mut scenario, &hp);
+    scenario.spawn_spider(runner(2), Vec2::new(0., 0.));
+    scenario.step_n(120);
+    assert_eq!(*scenario.state(), AppState::GameOver);
+    assert_eq!(garden(&scenario).hp, 0);
+}
+
+#[test]
+fn plants_grow_back_when_a_new_wave_starts() {
+    let mut scenario = Scenario::empty(0);
+    let mut hp = vec![PLANT_MAX_HP; PLANT_COUNT];
+    hp[0] = 0;
+    hp[1] = 1;
+    set_plants(&mut scenario, &hp);
+    scenario.step();
+    assert_eq!(plants(&mut scenario), hp);
+
+    let next_wave = WaveConfig::default().levels[1].from_spawned;
+    scenario
+        .app
+        .world
+        .resource_mut::<ScoreResource>()
+        .spiders_spawned = next_wave;
+    scenario.step();
+    hp[0] = PLANT_REGROW_HP;
+    hp[1] = 1 + PLANT_REGROW_HP;
+    assert_eq!(plants(&mut scenario), hp);
+    scenario.step();
+    assert_eq!(plants(&mut scenario), hp);
+}
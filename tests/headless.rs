@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    headless::headless_app, score::ScoreResource, slime::Slime, spider::Spider, AppState,
+};
+
+/// Ten minutes of game time at 60 updates per second.
+const MAX_UPDATES: usize = 60 * 60 * 10;
+
+#[test]
+fn game_runs_until_a_spider_reaches_the_garden() {
+    let mut app = headless_app(0);
+    let mut updates = 0;
+    while *app.world.resource::<State<AppState>>().current() == AppState::InGame {
+        assert!(updates < MAX_UPDATES, "the game never ended");
+        app.update();
+        updates += 1;
+    }
+    assert_eq!(
+        *app.world.resource::<State<AppState>>().current(),
+        AppState::GameOver
+    );
+    assert!(app.world.resource::<ScoreResource>().spiders_spawned > 0);
+    let slimes = app.world.query::<&Slime>().iter(&app.world).count();
+    assert_eq!(slimes, 16);
+    let spiders = app.world.query::<&Spider>().iter(&app.world).count();
+    assert!(spiders > 0);
+}
+
+fn spider_positions_after(seed: u64, updates: usize) -> Vec<(u32, Vec2)> {
+    let mut app = headless_app(seed);
+    for _ in 0..updates {
+        app.update();
+    }
+    let mut spiders: Vec<_> = app
+        .world
+        .query::<(&Spider, &Transform)>()
+        .iter(&app.world)
+        .map(|(spider, transform)| (spider.level, transform.translation.truncate()))
+        .collect();
+    spiders.sort_by(|a, b| a.1.x.total_cmp(&b.1.x));
+    spiders
+}
+
+#[test]
+fn same_seed_plays_out_the_same() {
+    let first = spider_positions_after(7, 60 * 20);
+    assert!(!first.is_empty());
+    assert_eq!(first, spider_positions_after(7, 60 * 20));
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    scenario::Scenario,
+    slime::{SlimeColor, SlimeText},
+    spider::SpiderText,
+};
+
+mod common;
+
+use common::{slime, spider};
+
+fn label_count<T: Component>(scenario: &mut Scenario) -> usize {
+    scenario
+        .app
+        .world
+        .query_filtered::<(), With<T>>()
+        .iter(&scenario.app.world)
+        .count()
+}
+
+fn has_label<T: Component>(scenario: &Scenario, entity: Entity) -> bool {
+    let world = &scenario.app.world;
+    world
+        .get::<Children>(entity)
+        .unwrap()
+        .iter()
+        .any(|&child| world.get::<T>(child).is_some())
+}
+
+#[test]
+fn slimes_and_spiders_carry_their_labels() {
+    let mut scenario = Scenario::empty(0);
+    let red = scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::ZERO);
+    let crawler = scenario.spawn_spider(spider(2, SlimeColor::Blue), Vec2::new(300., 0.));
+    assert!(has_label::<SlimeText>(&scenario, red));
+    assert!(has_label::<SpiderText>(&scenario, crawler));
+}
+
+#[test]
+fn labels_go_away_with_their_slimes() {
+    let mut scenario = Scenario::empty(0);
+    let red = slime(SlimeColor::Red, 1);
+    let addition = scenario.spawn_slime(red, Vec2::new(-100., 0.));
+    let base = scenario.spawn_slime(red, Vec2::new(100., 0.));
+    scenario.drag_onto(addition, base).step();
+    assert_eq!(scenario.slimes().len(), 1);
+    assert_eq!(label_count::<SlimeText>(&mut scenario), 1);
+}
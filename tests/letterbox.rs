@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    camera::{Letterbox, PlayfieldAnchor},
+    PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH,
+};
+
+#[test]
+fn window_the_size_of_the_playfield_needs_no_bars() {
+    let letterbox = Letterbox::fit(Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT));
+    assert_eq!(letterbox, Letterbox::default());
+}
+
+#[test]
+fn wide_window_gets_bars_on_the_sides() {
+    let letterbox = Letterbox::fit(Vec2::new(1920., 540.));
+    assert_eq!(letterbox.scale, 0.75);
+    assert_eq!(letterbox.offset, Vec2::new(480., 0.));
+}
+
+#[test]
+fn tall_window_gets_bars_above_and_below() {
+    let letterbox = Letterbox::fit(Vec2::new(640., 1000.));
+    assert_eq!(letterbox.scale, 0.5);
+    assert_eq!(letterbox.offset, Vec2::new(0., 320.));
+}
+
+#[test]
+fn anchored_nodes_stay_on_the_playfield() {
+    let letterbox = Letterbox::fit(Vec2::new(1920., 540.));
+    assert_eq!(
+        PlayfieldAnchor::at(16., 8.).position(&letterbox),
+        Vec2::new(492., 6.)
+    );
+    let fill = PlayfieldAnchor::fill();
+    assert_eq!(fill.position(&letterbox), Vec2::new(480., 0.));
+    assert_eq!(fill.size(&letterbox), Some(Vec2::new(960., 540.)));
+}
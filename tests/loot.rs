@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    combine::SpiderKilledEvent,
+    loot::{
+        empowered, FieldFreeze, Pickup, PowerUp, PowerUpKind, FREEZE_DURATION_SECS,
+        PICKUP_LIFETIME_SECS, SIZE_UP_DURATION_SECS,
+    },
+    scenario::Scenario,
+    slime::SlimeColor,
+    spider::Spider,
+};
+
+mod common;
+
+use common::{slime, spider};
+
+/// Leaves a pickup of `kind` lying at `position`, as if a spider had dropped it.
+fn drop_pickup(scenario: &mut Scenario, kind: PowerUpKind, position: Vec2) -> Entity {
+    scenario
+        .app
+        .world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(
+            Transform::from_translation(position.extend(0.)),
+        ))
+        .insert(Pickup {
+            kind,
+            lifetime: Timer::from_seconds(PICKUP_LIFETIME_SECS, false),
+        })
+        .id()
+}
+
+fn pickups(scenario: &mut Scenario) -> usize {
+    let world = &mut scenario.app.world;
+    world.query::<&Pickup>().iter(world).count()
+}
+
+#[test]
+fn power_ups_change_what_slimes_attack_as() {
+    let red = slime(SlimeColor::Red, 2);
+    let blue = SlimeColor::Blue;
+    assert_eq!(empowered(&red, None, blue), red);
+    let potion = PowerUp::new(PowerUpKind::Potion(SlimeColor::Green));
+    assert_eq!(
+        empowered(&red, Some(&potion), blue),
+        slime(SlimeColor::Green, 2)
+    );
+    let size_up = PowerUp::new(PowerUpKind::SizeUp);
+    assert_eq!(
+        empowered(&red, Some(&size_up), blue),
+        slime(SlimeColor::Red, 3)
+    );
+    let rainbow = PowerUp::new(PowerUpKind::Rainbow);
+    assert_eq!(
+        empowered(&red, Some(&rainbow), blue),
+        slime(SlimeColor::Blue, 2)
+    );
+}
+
+#[test]
+fn killed_spiders_sometimes_drop_pickups() {
+    let mut scenario = Scenario::empty(0);
+    for i in 0..20 {
+        scenario.app.world.send_event(SpiderKilledEvent {
+            spider: spider(1, SlimeColor::Red),
+            position: Vec2::new(i as f32 * 10., 0.),
+        });
+    }
+    scenario.step();
+    let dropped = pickups(&mut scenario);
+    assert!(dropped > 0 && dropped < 20, "{dropped} pickups dropped");
+}
+
+#[test]
+fn pickups_vanish_if_nobody_takes_them() {
+    let mut scenario = Scenario::empty(0);
+    let pickup = drop_pickup(&mut scenario, PowerUpKind::SizeUp, Vec2::ZERO);
+    scenario.step_n(60);
+    assert!(scenario.exists(pickup));
+    scenario.step_n((PICKUP_LIFETIME_SECS * 60.) as usize);
+    assert!(!scenario.exists(pickup));
+}
+
+#[test]
+fn dragging_a_slime_over_a_pickup_powers_it_up_for_a_while() {
+    let mut scenario = Scenario::empty(0);
+    let entity = scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::new(-100., 0.));
+    let pickup = drop_pickup(&mut scenario, PowerUpKind::SizeUp, Vec2::ZERO);
+    scenario.drag(Vec2::new(-100., 0.), Vec2::new(100., 0.));
+    assert!(!scenario.exists(pickup));
+    assert_eq!(
+        scenario.app.world.get::<PowerUp>(entity).unwrap().kind,
+        PowerUpKind::SizeUp
+    );
+    scenario.step_n((SIZE_UP_DURATION_SECS * 60.) as usize);
+    assert!(scenario.app.world.get::<PowerUp>(entity).is_none());
+}
+
+#[test]
+fn a_rainbow_slime_hits_every_spider_where_it_is_weak() {
+    let mut scenario = Scenario::empty(0);
+    // a size 2 slime only kills a level 2 spider by hitting its weakness.
+    let target = scenario.spawn_spider(spider(2, SlimeColor::Blue), Vec2::new(200., 0.));
+    let attacker = scenario.spawn_slime(slime(SlimeColor::Red, 2), Vec2::new(-100., 0.));
+    scenario
+        .app
+        .world
+        .entity_mut(attacker)
+        .insert(PowerUp::new(PowerUpKind::Rainbow));
+    scenario.drag_onto(attacker, target).step_n(10);
+    assert_eq!(scenario.score().spiders_killed, 1);
+    assert!(scenario.spiders().is_empty());
+    // the power-up is used up with the slime, so its halves are plain red.
+    assert_eq!(
+        scenario.slimes(),
+        vec![slime(SlimeColor::Red, 1), slime(SlimeColor::Red, 1)]
+    );
+}
+
+#[test]
+fn power_ups_are_left_out_of_merges() {
+    let mut scenario = Scenario::empty(0);
+    let base = scenario.spawn_slime(slime(SlimeColor::Red, 2), Vec2::new(100., 0.));
+    let addition = scenario.spawn_slime(slime(SlimeColor::Red, 2), Vec2::new(-100., 0.));
+    scenario
+        .app
+        .world
+        .entity_mut(addition)
+        .insert(PowerUp::new(PowerUpKind::SizeUp));
+    scenario.drag_onto(addition, base).step_n(10);
+    assert_eq!(scenario.slimes(), vec![slime(SlimeColor::Red, 4)]);
+}
+
+#[test]
+fn a_freeze_holds_every_spider_still_until_it_wears_off() {
+    let mut scenario = Scenario::empty(0);
+    let crawler = scenario.spawn_spider(
+        Spider {
+            speed: 60.,
+            ..spider(1, SlimeColor::Red)
+        },
+        Vec2::new(300., 100.),
+    );
+    scenario.spawn_slime(slime(SlimeColor::Red, 1), Vec2::new(-100., -100.));
+    drop_pickup(&mut scenario, PowerUpKind::Freeze, Vec2::new(0., -100.));
+    scenario.drag(Vec2::new(-100., -100.), Vec2::new(100., -100.));
+    assert!(scenario.app.world.get_resource::<FieldFreeze>().is_some());
+
+    let frozen_at = scenario.position(crawler);
+    scenario.step_n(60);
+    assert!(scenario.position(crawler).distance(frozen_at) < 1.);
+    scenario.step_n((FREEZE_DURATION_SECS * 60.) as usize);
+    assert!(scenario.app.world.get_resource::<FieldFreeze>().is_none());
+    let thawed_at = scenario.position(crawler);
+    scenario.step_n(60);
+    assert!(scenario.position(crawler).distance(thawed_at) > 10.);
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    pathing::{spider_velocity, Pathing, SpiderMovement},
+    slime::SlimeColor,
+    spider::{Spider, SpiderKind},
+};
+
+const DT: f32 = 1. / 60.;
+
+fn spider(movement: SpiderMovement) -> Spider {
+    Spider {
+        level: 2,
+        weakness: SlimeColor::Red,
+        speed: 60.,
+        kind: SpiderKind::Common,
+        movement,
+    }
+}
+
+/// Walks `spider` from `start` for `seconds` and returns every position along the way.
+fn walk(spider: &Spider, start: Vec2, slimes: &[Vec2], seconds: f32) -> Vec<Vec2> {
+    let mut pathing = Pathing::new(start.y);
+    let mut position = start;
+    let mut path = vec![position];
+    for _ in 0..(seconds / DT).round() as usize {
+        position += spider_velocity(spider, &mut pathing, position, slimes, DT) * DT;
+        path.push(position);
+    }
+    path
+}
+
+fn assert_close(a: f32, b: f32, tolerance: f32) {
+    assert!(
+        (a - b).abs() <= tolerance,
+        "{a} is not within {tolerance} of {b}"
+    );
+}
+
+#[test]
+fn straight_spiders_walk_left() {
+    let path = walk(&spider(SpiderMovement::Straight), Vec2::ZERO, &[], 2.);
+    assert_close(path.last().unwrap().x, -120., 0.01);
+    assert!(path.iter().all(|position| position.y == 0.));
+}
+
+#[test]
+fn waves_stay_within_their_amplitude_and_come_back() {
+    for movement in [
+        SpiderMovement::SineWave {
+            amplitude: 50.,
+            period: 2.,
+        },
+        SpiderMovement::ZigZag {
+            amplitude: 50.,
+            period: 2.,
+        },
+    ] {
+        let path = walk(&spider(movement), Vec2::ZERO, &[], 2.);
+        let highest = path.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        let lowest = path.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+        assert_close(highest, 50., 3.);
+        assert_close(lowest, -50., 3.);
+        assert_close(path.last().unwrap().y, 0., 3.);
+        assert_close(path.last().unwrap().x, -120., 0.01);
+    }
+}
+
+#[test]
+fn lane_changers_move_a_lane_at_a_time() {
+    let movement = SpiderMovement::LaneChange {
+        lane_width: 100.,
+        interval: 2.,
+    };
+    let path = walk(&spider(movement), Vec2::ZERO, &[], 1.9);
+    assert_eq!(path.last().unwrap().y, 0.);
+    let path = walk(&spider(movement), Vec2::ZERO, &[], 3.9);
+    assert_close(path.last().unwrap().y, 100., 0.5);
+}
+
+#[test]
+fn lane_changers_turn_around_at_the_edge() {
+    let movement = SpiderMovement::LaneChange {
+        lane_width: 100.,
+        interval: 2.,
+    };
+    let path = walk(&spider(movement), Vec2::new(0., 250.), &[], 3.9);
+    assert_close(path.last().unwrap().y, 150., 0.5);
+}
+
+#[test]
+fn chargers_creep_then_rush() {
+    let movement = SpiderMovement::Charge {
+        multiplier: 3.,
+        interval: 4.,
+        duration: 1.,
+    };
+    let path = walk(&spider(movement), Vec2::ZERO, &[], 3.);
+    assert_close(path.last().unwrap().x, -90., 1.);
+    let path = walk(&spider(movement), Vec2::ZERO, &[], 4.);
+    assert_close(path.last().unwrap().x, -270., 2.);
+}
+
+#[test]
+fn avoiders_sidestep_slimes_ahead() {
+    let movement = SpiderMovement::Avoid { radius: 150. };
+    let slime_above = [Vec2::new(-100., 20.)];
+    let path = walk(&spider(movement), Vec2::ZERO, &slime_above, 1.);
+    assert!(path.last().unwrap().y < -10.);
+    let slime_behind = [Vec2::new(100., 20.)];
+    let path = walk(&spider(movement), Vec2::ZERO, &slime_behind, 1.);
+    assert_eq!(path.last().unwrap().y, 0.);
+}
+
+#[test]
+fn spiders_stay_on_the_field() {
+    let movement = SpiderMovement::SineWave {
+        amplitude: 500.,
+        period: 4.,
+    };
+    let path = walk(&spider(movement), Vec2::new(0., 200.), &[], 8.);
+    let limit = bevy_jam_2::PLAYFIELD_HEIGHT / 2.;
+    assert!(path.iter().all(|position| position.y.abs() < limit));
+}
@@ -0,0 +1,278 @@
+use std::time::Duration;
+
+use bevy::{
+    input::{mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+};
+use bevy_jam_2::{
+    headless::{headless_app, HeadlessPlugin, HeadlessTimestep},
+    interaction::{CombineEvent, MousePosition},
+    loot::{Pickup, PowerUp, PowerUpKind, PICKUP_LIFETIME_SECS},
+    replay::{play, RecordPlugin, Replay, ReplayPlayer, ReplayPlugin, ReplayRecorder},
+    score::ScoreResource,
+    slime::Slime,
+    spider::{SpawnSpiderEvent, Spider},
+    waves::{PerSpawn, WaveConfig},
+    AppState,
+};
+
+const MAX_UPDATES: usize = 60 * 60 * 10;
+
+/// What a run did, as far as these tests care.
+#[derive(Debug, Default, PartialEq)]
+struct RunLog {
+    combines: Vec<Vec2>,
+    spider_spawns: Vec<(u32, Vec2)>,
+    spiders_killed: u32,
+    spiders_spawned: u32,
+    power_ups: usize,
+}
+
+fn log_events(
+    mut log: ResMut<RunLog>,
+    mut combine_events: EventReader<CombineEvent>,
+    mut spider_events: EventReader<SpawnSpiderEvent>,
+    power_up_query: Query<(), Added<PowerUp>>,
+) {
+    log.power_ups += power_up_query.iter().count();
+    log.combines
+        .extend(combine_events.iter().map(|ev| ev.location));
+    log.spider_spawns.extend(
+        spider_events
+            .iter()
+            .map(|ev| (ev.spider.level, ev.position)),
+    );
+}
+
+fn add_log(app: &mut App) {
+    app.init_resource::<RunLog>()
+        .add_system_to_stage(CoreStage::PostUpdate, log_events);
+}
+
+fn finish_log(app: &mut App) -> RunLog {
+    let score = app.world.resource::<ScoreResource>();
+    let (spiders_killed, spiders_spawned) = (score.spiders_killed, score.spiders_spawned);
+    let mut log = app.world.remove_resource::<RunLog>().unwrap();
+    log.spiders_killed = spiders_killed;
+    log.spiders_spawned = spiders_spawned;
+    log
+}
+
+fn in_game(app: &App) -> bool {
+    *app.world.resource::<State<AppState>>().current() == AppState::InGame
+}
+
+fn mouse_button(app: &mut App, state: ButtonState) {
+    app.world.send_event(MouseButtonInput {
+        button: MouseButton::Left,
+        state,
+    });
+}
+
+fn record_run(seed: u64) -> (Replay, RunLog) {
+    record_run_with(headless_app(seed))
+}
+
+/// Drags the leftmost slime onto the next slime or spider every so often, with uneven frame
+/// lengths, until the run on `app` ends.
+fn record_run_with(app: App) -> (Replay, RunLog) {
+    record_run_changing(app, |_, _| {})
+}
+
+/// As [`record_run_with`], calling `change` with the number of updates so far before each one.
+fn record_run_changing(mut app: App, mut change: impl FnMut(&mut App, usize)) -> (Replay, RunLog) {
+    app.add_plugin(RecordPlugin::default());
+    add_log(&mut app);
+    let mut updates = 0;
+    while in_game(&app) {
+        assert!(updates < MAX_UPDATES, "the game never ended");
+        let nanos = if updates % 3 == 0 {
+            20_000_000
+        } else {
+            15_000_000
+        };
+        app.world.resource_mut::<HeadlessTimestep>().0 = Duration::from_nanos(nanos);
+        match updates % 90 {
+            30 => {
+                let mut slimes: Vec<Vec2> = app
+                    .world
+                    .query_filtered::<&Transform, With<Slime>>()
+                    .iter(&app.world)
+                    .map(|transform| transform.translation.truncate())
+                    .collect();
+                slimes.sort_by(|a, b| a.x.total_cmp(&b.x));
+                app.world.resource_mut::<MousePosition>().0 = slimes.first().copied();
+                mouse_button(&mut app, ButtonState::Pressed);
+            }
+            40 => {
+                let target = if updates % 180 == 40 {
+                    app.world
+                        .query_filtered::<&Transform, With<Spider>>()
+                        .iter(&app.world)
+                        .next()
+                } else {
+                    app.world
+                        .query_filtered::<&Transform, With<Slime>>()
+                        .iter(&app.world)
+                        .nth(3)
+                };
+                let target = target.map(|transform| transform.translation.truncate());
+                app.world.resource_mut::<MousePosition>().0 = target;
+                mouse_button(&mut app, ButtonState::Released);
+            }
+            _ => {}
+        }
+        change(&mut app, updates);
+        app.update();
+        updates += 1;
+    }
+    let replay = app
+        .world
+        .resource::<ReplayRecorder>()
+        .finished
+        .clone()
+        .expect("the run was recorded");
+    (replay, finish_log(&mut app))
+}
+
+/// Leaves a size-up pickup lying at `position`, as if a spider had dropped it.
+fn drop_pickup(app: &mut App, position: Vec2) {
+    app.world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(
+            Transform::from_translation(position.extend(0.)),
+        ))
+        .insert(Pickup {
+            kind: PowerUpKind::SizeUp,
+            lifetime: Timer::from_seconds(PICKUP_LIFETIME_SECS, false),
+        });
+}
+
+fn replay_run(replay: Replay) -> RunLog {
+    replay_run_with(replay, |_| {})
+}
+
+/// Plays `replay` back, calling `setup` after the first frame as the recording did.
+fn replay_run_with(replay: Replay, setup: impl FnOnce(&mut App)) -> RunLog {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(ReplayPlugin { replay })
+        .add_plugin(HeadlessPlugin::default());
+    add_log(&mut app);
+    app.update();
+    setup(&mut app);
+    while !app.world.resource::<ReplayPlayer>().finished() {
+        app.update();
+    }
+    assert!(!in_game(&app));
+    finish_log(&mut app)
+}
+
+#[test]
+fn replay_reproduces_the_recorded_run() {
+    let (replay, recorded) = record_run(3);
+    assert!(!recorded.combines.is_empty());
+    assert!(!recorded.spider_spawns.is_empty());
+    assert_eq!(replay.seed, 3);
+    assert_eq!(replay_run(replay), recorded);
+}
+
+#[test]
+fn replay_plays_the_waves_the_run_was_recorded_with() {
+    let waves = WaveConfig {
+        interval: PerSpawn {
+            base: 2.,
+            ..WaveConfig::default().interval
+        },
+        ..default()
+    };
+    let mut app = headless_app(6);
+    app.insert_resource(waves.clone());
+    let (replay, recorded) = record_run_with(app);
+    assert_eq!(replay.waves, waves);
+    assert_eq!(replay_run(replay), recorded);
+}
+
+#[test]
+fn replay_follows_the_waves_reloading_mid_run() {
+    let faster = WaveConfig {
+        interval: PerSpawn {
+            base: 1.,
+            ..WaveConfig::default().interval
+        },
+        ..default()
+    };
+    let (replay, recorded) = record_run_changing(headless_app(6), |app, updates| {
+        // as `WavesPlugin` does when `waves.ron` is edited.
+        if updates == 600 {
+            app.insert_resource(faster.clone());
+        }
+    });
+    assert_eq!(replay.waves, WaveConfig::default());
+    assert_eq!(replay.wave_changes.len(), 1);
+    assert_eq!(replay.wave_changes[0].waves, faster);
+    let score = play(replay.clone());
+    assert_eq!(score.spiders_spawned, recorded.spiders_spawned);
+    assert_eq!(score.spiders_killed, recorded.spiders_killed);
+    assert_eq!(replay_run(replay), recorded);
+}
+
+#[test]
+fn playing_a_replay_scores_it_as_recorded() {
+    let (replay, recorded) = record_run(7);
+    let score = play(replay);
+    assert_eq!(score.spiders_killed, recorded.spiders_killed);
+    assert_eq!(score.spiders_spawned, recorded.spiders_spawned);
+}
+
+#[test]
+fn replay_survives_a_round_trip_through_ron() {
+    let (replay, _) = record_run(4);
+    let ron = replay.to_ron().unwrap();
+    assert_eq!(Replay::from_ron(&ron).unwrap(), replay);
+}
+
+#[test]
+fn replay_follows_the_mouse_through_a_drag() {
+    let mut app = headless_app(5);
+    app.add_plugin(RecordPlugin::default());
+    add_log(&mut app);
+    app.update();
+    let start = app
+        .world
+        .query_filtered::<&Transform, With<Slime>>()
+        .iter(&app.world)
+        .map(|transform| transform.translation.truncate())
+        .min_by(|a, b| a.x.total_cmp(&b.x))
+        .unwrap();
+    // the pickup is only passed over on the way, not where the drag starts or ends.
+    let pickup = start + Vec2::new(0., 150.);
+    let setup = |app: &mut App| drop_pickup(app, pickup);
+    setup(&mut app);
+
+    app.world.resource_mut::<MousePosition>().0 = Some(start);
+    mouse_button(&mut app, ButtonState::Pressed);
+    app.update();
+    for step in 1..=10 {
+        let position = start.lerp(pickup, step as f32 / 10.);
+        app.world.resource_mut::<MousePosition>().0 = Some(position);
+        app.update();
+    }
+    app.world.resource_mut::<MousePosition>().0 = Some(start + Vec2::new(-60., 0.));
+    mouse_button(&mut app, ButtonState::Released);
+    let mut updates = 0;
+    while in_game(&app) {
+        assert!(updates < MAX_UPDATES, "the game never ended");
+        app.update();
+        updates += 1;
+    }
+    let replay = app
+        .world
+        .resource::<ReplayRecorder>()
+        .finished
+        .clone()
+        .expect("the run was recorded");
+    let recorded = finish_log(&mut app);
+    assert_eq!(recorded.power_ups, 1);
+    assert_eq!(replay_run_with(replay, setup), recorded);
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    scenario::Scenario,
+    score::{
+        ScoreResource, CLOSE_CALL_BONUS, CLOSE_CALL_PX, MAX_MULTIPLIER, POINTS_PER_LEVEL,
+        STREAK_PER_MULTIPLIER,
+    },
+    slime::SlimeColor,
+    GARDEN_X,
+};
+
+mod common;
+
+use common::{slime, spider};
+
+/// Somewhere well away from the garden.
+const FAR: Vec2 = Vec2::new(300., 0.);
+
+#[test]
+fn kills_score_by_spider_level() {
+    let mut score = ScoreResource::default();
+    score.attack(3);
+    assert_eq!(
+        score.kill(&spider(3, SlimeColor::Red), FAR),
+        3 * POINTS_PER_LEVEL
+    );
+    assert_eq!(score.points, 3 * POINTS_PER_LEVEL);
+}
+
+#[test]
+fn streaks_raise_the_multiplier_until_an_attack_does_no_damage() {
+    let mut score = ScoreResource::default();
+    for _ in 0..STREAK_PER_MULTIPLIER - 1 {
+        score.attack(1);
+        score.kill(&spider(1, SlimeColor::Red), FAR);
+    }
+    // hurting a spider without killing it keeps the streak going.
+    score.attack(1);
+    assert_eq!(score.multiplier(), 2);
+    score.attack(1);
+    assert_eq!(
+        score.kill(&spider(1, SlimeColor::Red), FAR),
+        2 * POINTS_PER_LEVEL
+    );
+    score.attack(0);
+    assert_eq!(score.streak, 0);
+    assert_eq!(score.multiplier(), 1);
+    assert_eq!(score.best_streak, STREAK_PER_MULTIPLIER + 1);
+}
+
+#[test]
+fn the_multiplier_is_capped() {
+    let mut score = ScoreResource::default();
+    for _ in 0..STREAK_PER_MULTIPLIER * (MAX_MULTIPLIER + 2) {
+        score.attack(1);
+    }
+    assert_eq!(score.multiplier(), MAX_MULTIPLIER);
+}
+
+#[test]
+fn kills_close_to_the_garden_earn_a_bonus() {
+    let mut score = ScoreResource::default();
+    let close = Vec2::new(GARDEN_X + CLOSE_CALL_PX / 2., 0.);
+    score.attack(1);
+    assert_eq!(
+        score.kill(&spider(1, SlimeColor::Red), close),
+        POINTS_PER_LEVEL + CLOSE_CALL_BONUS
+    );
+    assert_eq!(score.close_calls, 1);
+    assert_eq!(
+        score.kill(&spider(1, SlimeColor::Red), FAR),
+        POINTS_PER_LEVEL
+    );
+    assert_eq!(score.close_calls, 1);
+}
+
+#[test]
+fn runs_keep_score_of_attacks_and_time() {
+    let mut scenario = Scenario::empty(0);
+    let target = scenario.spawn_spider(spider(2, SlimeColor::Red), FAR);
+    let attacker = scenario.spawn_slime(slime(SlimeColor::Red, 2), Vec2::new(-100., 0.));
+    scenario.drag_onto(attacker, target).step_n(60);
+    let score = scenario.score();
+    assert_eq!(score.spiders_killed, 1);
+    assert_eq!(score.points, 2 * POINTS_PER_LEVEL);
+    assert_eq!(score.streak, 1);
+    assert!(score.seconds_survived >= 1.);
+}
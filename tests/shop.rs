@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    interaction::DragActive,
+    scenario::Scenario,
+    shop::{goo_reward, Goo, ShopSlot, SLIME_PRICE, STARTING_GOO},
+    slime::SlimeColor,
+};
+
+mod common;
+
+use common::{slime, spider};
+
+fn goo(scenario: &Scenario) -> u32 {
+    scenario.app.world.resource::<Goo>().0
+}
+
+/// Clicks the shop slot for `color`, letting go straight away.
+fn buy(scenario: &mut Scenario, color: SlimeColor) {
+    let slot = ShopSlot::position(color);
+    scenario.press(slot).release(slot).step();
+}
+
+#[test]
+fn killing_a_spider_earns_goo_for_its_level() {
+    let mut scenario = Scenario::empty(0);
+    assert_eq!(goo(&scenario), STARTING_GOO);
+    let target = scenario.spawn_spider(spider(2, SlimeColor::Red), Vec2::new(300., 0.));
+    let attacker = scenario.spawn_slime(slime(SlimeColor::Red, 2), Vec2::new(-100., 0.));
+    scenario.drag_onto(attacker, target).step();
+    assert!(!scenario.exists(target));
+    assert_eq!(
+        goo(&scenario),
+        STARTING_GOO + goo_reward(&spider(2, SlimeColor::Red))
+    );
+}
+
+#[test]
+fn clicking_a_slot_buys_a_slime_of_its_color() {
+    let mut scenario = Scenario::empty(0);
+    scenario.app.world.resource_mut::<Goo>().0 = SLIME_PRICE + 1;
+    buy(&mut scenario, SlimeColor::Cyan);
+    assert_eq!(goo(&scenario), 1);
+    assert_eq!(scenario.slimes(), vec![slime(SlimeColor::Cyan, 1)]);
+}
+
+#[test]
+fn slimes_cost_goo_the_player_has() {
+    let mut scenario = Scenario::empty(0);
+    scenario.app.world.resource_mut::<Goo>().0 = SLIME_PRICE - 1;
+    buy(&mut scenario, SlimeColor::Cyan);
+    assert_eq!(goo(&scenario), SLIME_PRICE - 1);
+    assert!(scenario.slimes().is_empty());
+}
+
+#[test]
+fn clicking_a_slot_never_picks_up_the_slime_under_it() {
+    let mut scenario = Scenario::empty(0);
+    let slot = ShopSlot::position(SlimeColor::Cyan);
+    let under = scenario.spawn_slime(slime(SlimeColor::Red, 1), slot);
+    scenario.press(slot);
+    scenario.move_mouse(Some(slot + Vec2::new(200., 0.))).step();
+    assert!(!scenario.app.world.get::<DragActive>(under).unwrap().0);
+    assert!(scenario.position(under).distance(slot) < 10.);
+}
@@ -0,0 +1,43 @@
+use bevy_jam_2::simulation::{simulate, GreedyBot, IdleBot, LossCause, SimulationConfig};
+
+#[test]
+fn idle_bot_loses_to_the_first_spiders() {
+    let report = simulate(1, &mut IdleBot, &SimulationConfig::default());
+    assert!(matches!(report.cause, LossCause::GardenReached { .. }));
+    assert_eq!(report.spiders_killed, 0);
+    assert!(report.spiders_spawned > 0);
+}
+
+#[test]
+fn greedy_bot_kills_spiders_and_outlasts_the_idle_bot() {
+    let config = SimulationConfig::default();
+    for seed in [2, 3] {
+        let idle = simulate(seed, &mut IdleBot, &config);
+        let greedy = simulate(seed, &mut GreedyBot, &config);
+        assert!(greedy.spiders_killed > 0, "seed {seed}");
+        assert!(
+            greedy.survival_seconds > idle.survival_seconds,
+            "seed {seed}"
+        );
+    }
+}
+
+#[test]
+fn simulation_stops_at_the_time_limit() {
+    let config = SimulationConfig {
+        max_seconds: 5.,
+        ..Default::default()
+    };
+    let report = simulate(3, &mut IdleBot, &config);
+    assert_eq!(report.cause, LossCause::Timeout);
+    assert!(report.survival_seconds >= 5.);
+}
+
+#[test]
+fn same_seed_gives_the_same_report() {
+    let config = SimulationConfig::default();
+    assert_eq!(
+        simulate(4, &mut GreedyBot, &config),
+        simulate(4, &mut GreedyBot, &config)
+    );
+}
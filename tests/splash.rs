@@ -0,0 +1,204 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    scenario::Scenario,
+    slime::SlimeColor,
+    spider::{Spider, SpiderHealth, SpiderKind},
+    splash::{splash, SplashConfig, SplashTarget},
+};
+
+mod common;
+
+use common::{slime, spider};
+
+/// A target standing in for entity `id`.
+fn target(id: u32, spider: Spider, x: f32) -> SplashTarget {
+    SplashTarget {
+        entity: Entity::from_raw(id),
+        position: Vec2::new(x, 0.),
+        spider,
+        hp: SpiderHealth::full(spider.level).hp,
+    }
+}
+
+fn enabled() -> SplashConfig {
+    SplashConfig {
+        enabled: true,
+        ..default()
+    }
+}
+
+#[test]
+fn only_big_kills_of_a_weakness_splash() {
+    let config = enabled();
+    let red = spider(1, SlimeColor::Red);
+    assert!(config.splashes(&slime(SlimeColor::Red, config.min_size), &red));
+    assert!(!config.splashes(&slime(SlimeColor::Red, config.min_size - 1), &red));
+    assert!(!config.splashes(&slime(SlimeColor::Blue, 5), &red));
+}
+
+#[test]
+fn splashes_fall_off_towards_their_edge() {
+    let config = SplashConfig {
+        radius_px: 100.,
+        falloff: 0.5,
+        ..enabled()
+    };
+    let big = slime(SlimeColor::Red, 4);
+    let common = spider(1, SlimeColor::Blue);
+    assert_eq!(config.damage(&big, &common, 0.), 4);
+    assert_eq!(config.damage(&big, &common, 50.), 3);
+    assert_eq!(config.damage(&big, &common, 100.), 2);
+    assert_eq!(config.damage(&big, &common, 101.), 0);
+    let flat = SplashConfig {
+        falloff: 0.,
+        ..config
+    };
+    assert_eq!(flat.damage(&big, &common, 100.), 4);
+    let armored = Spider {
+        kind: SpiderKind::Armored,
+        ..common
+    };
+    assert_eq!(config.damage(&slime(SlimeColor::Red, 2), &armored, 0.), 0);
+}
+
+#[test]
+fn a_splash_hits_everything_in_reach() {
+    let config = SplashConfig {
+        bounce_px: 0.,
+        ..enabled()
+    };
+    let targets = [
+        target(0, spider(1, SlimeColor::Blue), 60.),
+        target(1, spider(5, SlimeColor::Blue), -60.),
+        target(2, spider(1, SlimeColor::Blue), 300.),
+    ];
+    let big = slime(SlimeColor::Red, 4);
+    let chain = splash(
+        &config,
+        &big,
+        vec![slime(SlimeColor::Red, 2); 2],
+        &spider(2, SlimeColor::Red),
+        Vec2::ZERO,
+        &targets,
+    );
+    assert_eq!(
+        chain.damage,
+        vec![(targets[0].entity, 2), (targets[1].entity, 3)]
+    );
+    assert_eq!(chain.killed, vec![targets[0]]);
+    assert_eq!(chain.splashes, vec![Vec2::ZERO]);
+    // nothing was near enough to bounce into.
+    assert_eq!(
+        chain.landed,
+        vec![
+            (slime(SlimeColor::Red, 2), Vec2::ZERO),
+            (slime(SlimeColor::Red, 2), Vec2::ZERO)
+        ]
+    );
+}
+
+#[test]
+fn halves_bounce_into_the_nearest_spiders() {
+    let config = enabled();
+    let targets = [target(0, spider(3, SlimeColor::Blue), 100.)];
+    let big = slime(SlimeColor::Red, 4);
+    let chain = splash(
+        &config,
+        &big,
+        vec![slime(SlimeColor::Red, 2); 2],
+        &spider(2, SlimeColor::Red),
+        Vec2::ZERO,
+        &targets,
+    );
+    // 2 from the splash, then 2 from each half.
+    assert_eq!(chain.damage, vec![(targets[0].entity, 6)]);
+    assert_eq!(chain.killed, vec![targets[0]]);
+    let at = targets[0].position;
+    assert_eq!(
+        chain.landed,
+        vec![
+            (slime(SlimeColor::Red, 1), at),
+            (slime(SlimeColor::Red, 1), at),
+            (slime(SlimeColor::Red, 1), at),
+            (slime(SlimeColor::Red, 1), at)
+        ]
+    );
+}
+
+#[test]
+fn bounces_that_kill_big_enough_splash_again() {
+    let config = SplashConfig {
+        radius_px: 50.,
+        bounce_px: 120.,
+        ..enabled()
+    };
+    let targets = [
+        target(0, spider(3, SlimeColor::Red), 100.),
+        target(1, spider(1, SlimeColor::Blue), 140.),
+        target(2, spider(1, SlimeColor::Blue), 400.),
+    ];
+    let huge = slime(SlimeColor::Red, 6);
+    let chain = splash(
+        &config,
+        &huge,
+        vec![slime(SlimeColor::Red, 3); 2],
+        &spider(2, SlimeColor::Red),
+        Vec2::ZERO,
+        &targets,
+    );
+    assert_eq!(chain.killed, vec![targets[0], targets[1]]);
+    assert_eq!(chain.splashes, vec![Vec2::ZERO, targets[0].position]);
+    assert!(chain
+        .damage
+        .iter()
+        .all(|&(entity, _)| entity != targets[2].entity));
+    let total: u32 = chain.landed.iter().map(|(slime, _)| slime.size).sum();
+    assert_eq!(total, 6);
+}
+
+#[test]
+fn brood_mothers_hatch_when_a_splash_kills_them() {
+    let config = enabled();
+    let mother = Spider {
+        kind: SpiderKind::BroodMother,
+        ..spider(2, SlimeColor::Blue)
+    };
+    let targets = [SplashTarget {
+        hp: 1,
+        ..target(0, mother, 50.)
+    }];
+    let chain = splash(
+        &config,
+        &slime(SlimeColor::Red, 4),
+        vec![slime(SlimeColor::Red, 2); 2],
+        &spider(2, SlimeColor::Red),
+        Vec2::ZERO,
+        &targets,
+    );
+    assert_eq!(chain.killed, vec![targets[0]]);
+    assert_eq!(chain.brood.len(), 2);
+    assert!(chain.brood.iter().all(|(spider, _)| spider.level == 1));
+}
+
+fn splash_run(config: SplashConfig) -> Scenario {
+    let mut scenario = Scenario::empty(0);
+    scenario.app.insert_resource(config);
+    let struck = scenario.spawn_spider(spider(2, SlimeColor::Red), Vec2::new(200., 0.));
+    scenario.spawn_spider(spider(1, SlimeColor::Blue), Vec2::new(200., 70.));
+    scenario.spawn_spider(spider(1, SlimeColor::Blue), Vec2::new(200., -300.));
+    let attacker = scenario.spawn_slime(slime(SlimeColor::Red, 4), Vec2::new(-200., 0.));
+    scenario.drag_onto(attacker, struck).step_n(10);
+    scenario
+}
+
+#[test]
+fn kills_only_splash_when_enabled() {
+    let mut scenario = splash_run(enabled());
+    assert_eq!(scenario.score().spiders_killed, 2);
+    assert_eq!(scenario.score().streak, 2);
+    assert_eq!(scenario.spiders().len(), 1);
+
+    let mut scenario = splash_run(SplashConfig::default());
+    assert_eq!(scenario.score().spiders_killed, 1);
+    assert_eq!(scenario.spiders().len(), 2);
+}
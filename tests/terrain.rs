@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use bevy_jam_2::{
+    pathing::{detour, spider_velocity, Detour, Pathing, SpiderMovement},
+    scenario::Scenario,
+    slime::{Slime, SlimeColor},
+    spider::{Spider, SpiderKind, SPIDER_RADIUS_PX},
+    terrain::{
+        random_obstacles, Obstacle, TerrainKind, TerrainLayout, RANDOM_COLUMNS,
+        RANDOM_OBSTACLE_COUNT, RANDOM_ROWS,
+    },
+};
+use bevy_rapier2d::prelude::Velocity;
+use rand::{rngs::StdRng, SeedableRng};
+
+const DT: f32 = 1. / 60.;
+
+fn spider(level: u32) -> Spider {
+    Spider {
+        level,
+        weakness: SlimeColor::Red,
+        speed: 120.,
+        kind: SpiderKind::Common,
+        movement: SpiderMovement::Straight,
+    }
+}
+
+fn obstacle(kind: TerrainKind, column: i32, row: i32, width: u32, height: u32) -> Obstacle {
+    Obstacle {
+        kind,
+        column,
+        row,
+        width,
+        height,
+    }
+}
+
+/// Walks `spider` from `start` for `seconds` past `obstacles`, and returns every position along
+/// the way.
+fn walk(spider: &Spider, start: Vec2, obstacles: &[Obstacle], seconds: f32) -> Vec<Vec2> {
+    let mut pathing = Pathing::new(start.y);
+    let mut position = start;
+    let mut path = vec![position];
+    for _ in 0..(seconds / DT).round() as usize {
+        let wanted = spider_velocity(spider, &mut pathing, position, &[], DT);
+        position += detour(spider.level, &mut pathing, position, wanted, obstacles) * DT;
+        path.push(position);
+    }
+    path
+}
+
+#[test]
+fn random_obstacles_leave_a_way_past() {
+    for seed in 0..50 {
+        let obstacles = random_obstacles(&mut StdRng::seed_from_u64(seed));
+        assert!(!obstacles.is_empty() && obstacles.len() <= RANDOM_OBSTACLE_COUNT);
+        for (i, a) in obstacles.iter().enumerate() {
+            assert!(a.column >= RANDOM_COLUMNS.0);
+            assert!(a.column + a.width as i32 - 1 <= RANDOM_COLUMNS.1);
+            assert!(a.row >= RANDOM_ROWS.0);
+            assert!(a.row + a.height as i32 - 1 <= RANDOM_ROWS.1);
+            for b in &obstacles[i + 1..] {
+                let apart = (a.center() - b.center()).abs() - a.half_size() - b.half_size();
+                assert!(apart.max_element() >= 64., "{a:?} and {b:?} touch");
+                if a.kind.blocks_spiders() && b.kind.blocks_spiders() {
+                    assert!(apart.x >= 0., "{a:?} and {b:?} wall off a column");
+                }
+            }
+        }
+        assert_eq!(
+            obstacles,
+            random_obstacles(&mut StdRng::seed_from_u64(seed))
+        );
+    }
+}
+
+#[test]
+fn shipped_maps_load() {
+    let ron = std::fs::read_to_string("assets/maps/crossing.ron").unwrap();
+    let TerrainLayout::Map(obstacles) = TerrainLayout::from_ron(&ron).unwrap() else {
+        panic!("not a map");
+    };
+    assert_eq!(obstacles.len(), 5);
+}
+
+#[test]
+fn spiders_go_around_rocks_and_ponds() {
+    for kind in [TerrainKind::Rock, TerrainKind::Pond] {
+        let rock = obstacle(kind, 0, 0, 2, 2);
+        let start = Vec2::new(300., rock.center().y + 10.);
+        let path = walk(&spider(1), start, &[rock], 10.);
+        let radius = 2. * SPIDER_RADIUS_PX;
+        assert!(path
+            .iter()
+            .all(|&position| !rock.overlaps(position, radius)));
+        // it went around the side it was already on.
+        assert!(path
+            .iter()
+            .any(|position| position.y > rock.center().y + 64.));
+        assert!(path.last().unwrap().x < rock.center().x - 64.);
+    }
+}
+
+#[test]
+fn spiders_crowd_through_gaps() {
+    let walls = [
+        obstacle(TerrainKind::Rock, 0, 2, 1, 4),
+        obstacle(TerrainKind::Rock, 0, -5, 1, 4),
+    ];
+    let path = walk(&spider(1), Vec2::new(300., 250.), &walls, 10.);
+    let end = *path.last().unwrap();
+    assert!(end.x < -64.);
+    assert!(end.y.abs() < 96., "went through the gap at {end}");
+}
+
+#[test]
+fn walled_in_spiders_trample_over_rocks() {
+    let wall = obstacle(TerrainKind::Rock, 0, -6, 1, 13);
+    let mut pathing = Pathing::new(0.);
+    let mut position = Vec2::new(100., 0.);
+    for _ in 0..600 {
+        let wanted = spider_velocity(&spider(1), &mut pathing, position, &[], DT);
+        position += detour(1, &mut pathing, position, wanted, &[wall]) * DT;
+    }
+    assert!(position.x < -64.);
+    assert_eq!(pathing.detour, Detour::None);
+}
+
+#[test]
+fn bushes_slow_spiders_down() {
+    let bush = obstacle(TerrainKind::Bush, -2, -2, 5, 5);
+    let open = walk(&spider(1), Vec2::new(100., 0.), &[], 1.);
+    let slowed = walk(&spider(1), Vec2::new(100., 0.), &[bush], 1.);
+    let distance = |path: &[Vec2]| path[0].x - path.last().unwrap().x;
+    assert!((distance(&slowed) - 0.5 * distance(&open)).abs() < 1.);
+}
+
+#[test]
+fn open_scenarios_have_no_obstacles() {
+    let mut scenario = Scenario::empty(0);
+    let world = &mut scenario.app.world;
+    assert_eq!(world.query::<&Obstacle>().iter(world).count(), 0);
+
+    let mut scenario = Scenario::with_terrain(0, TerrainLayout::Random);
+    let world = &mut scenario.app.world;
+    assert!(world.query::<&Obstacle>().iter(world).count() > 0);
+}
+
+#[test]
+fn rocks_stop_slimes_and_bushes_slow_them() {
+    let rock = obstacle(TerrainKind::Rock, 0, 0, 1, 1);
+    let bush = obstacle(TerrainKind::Bush, 0, 3, 2, 1);
+    let mut scenario = Scenario::with_terrain(0, TerrainLayout::Map(vec![rock, bush]));
+    let slime = Slime {
+        color: SlimeColor::Red,
+        size: 1,
+    };
+    let blocked = scenario.spawn_slime(slime, Vec2::new(-150., 0.));
+    let bogged = scenario.spawn_slime(slime, Vec2::new(-150., bush.center().y));
+    let free = scenario.spawn_slime(slime, Vec2::new(-150., -200.));
+    for entity in [blocked, bogged, free] {
+        scenario
+            .app
+            .world
+            .get_mut::<Velocity>(entity)
+            .unwrap()
+            .linvel = Vec2::new(600., 0.);
+    }
+    scenario.step_n(120);
+    assert!(scenario.position(blocked).x < rock.center().x);
+    assert!(scenario.position(bogged).x < scenario.position(free).x - 50.);
+}
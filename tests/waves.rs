@@ -0,0 +1,100 @@
+use bevy_jam_2::{pathing::SpiderMovement, spider::SpiderKind, waves::WaveConfig};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn shipped_waves_match_the_built_in_ladder() {
+    let ron = std::fs::read_to_string("assets/waves.ron").unwrap();
+    assert_eq!(WaveConfig::from_ron(&ron).unwrap(), WaveConfig::default());
+}
+
+#[test]
+fn levels_follow_the_last_step_reached() {
+    let waves = WaveConfig::from_ron(
+        "(
+            levels: [(from_spawned: 0, min: 1, max: 1), (from_spawned: 3, min: 4, max: 5)],
+            interval: (base: 1.0, per_spawn: 0.0),
+            speed: (base: 1.0, per_spawn: 0.0),
+        )",
+    )
+    .unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(waves.level(2, &mut rng), 1);
+    for spawned in 3..50 {
+        assert!((4..=5).contains(&waves.level(spawned, &mut rng)));
+    }
+}
+
+#[test]
+fn interval_is_clamped() {
+    let waves = WaveConfig::default();
+    assert_eq!(waves.interval(0).as_secs_f32(), 5.);
+    assert_eq!(waves.interval(100).as_secs_f32(), 1.);
+}
+
+#[test]
+fn inverted_level_range_is_rejected() {
+    let result = WaveConfig::from_ron(
+        "(
+            levels: [(from_spawned: 0, min: 3, max: 2)],
+            interval: (base: 1.0, per_spawn: 0.0),
+            speed: (base: 1.0, per_spawn: 0.0),
+        )",
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn kinds_only_show_up_once_reached() {
+    let waves = WaveConfig::from_ron(
+        "(
+            levels: [(from_spawned: 0, min: 1, max: 1)],
+            interval: (base: 1.0, per_spawn: 0.0),
+            speed: (base: 1.0, per_spawn: 0.0),
+            kinds: [
+                (kind: Common, weight: 1),
+                (kind: Scout, weight: 1000, from_spawned: 5),
+            ],
+        )",
+    )
+    .unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    for spawned in 0..5 {
+        assert_eq!(waves.kind(spawned, &mut rng), SpiderKind::Common);
+    }
+    let scouts = (0..100)
+        .filter(|_| waves.kind(5, &mut rng) == SpiderKind::Scout)
+        .count();
+    assert!(scouts > 90);
+}
+
+#[test]
+fn every_kind_can_show_up_eventually() {
+    let waves = WaveConfig::default();
+    let mut rng = StdRng::seed_from_u64(0);
+    let seen: Vec<SpiderKind> = (0..1000).map(|_| waves.kind(100, &mut rng)).collect();
+    for kind in SpiderKind::ALL {
+        assert!(seen.contains(&kind), "{kind:?} never showed up");
+    }
+}
+
+#[test]
+fn waves_pick_movements_like_kinds() {
+    let waves = WaveConfig::from_ron(
+        "(
+            levels: [(from_spawned: 0, min: 1, max: 1)],
+            interval: (base: 1.0, per_spawn: 0.0),
+            speed: (base: 1.0, per_spawn: 0.0),
+            movements: [
+                (movement: Straight, weight: 1),
+                (movement: Avoid(radius: 10.0), weight: 1000, from_spawned: 5),
+            ],
+        )",
+    )
+    .unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(waves.movement(4, &mut rng), SpiderMovement::Straight);
+    assert_eq!(
+        waves.movement(5, &mut rng),
+        SpiderMovement::Avoid { radius: 10. }
+    );
+}